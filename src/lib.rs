@@ -1,13 +1,25 @@
-use axum::{Router, extract::Request, http::StatusCode, response::Response};
+use axum::{
+    Router,
+    extract::{FromRequestParts, OptionalFromRequestParts, Request},
+    handler::Handler,
+    http::{HeaderName, HeaderValue, Method, StatusCode, Uri, header, request::Parts},
+    response::{IntoResponse, Response},
+};
 use futures::future::BoxFuture;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::Infallible,
-    sync::Arc,
+    fmt,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
     task::{Context, Poll},
+    time::{Duration, Instant, SystemTime},
 };
+use tokio::sync::mpsc;
 use tower::util::ServiceExt;
 use tower::{Layer, Service};
 
@@ -21,36 +33,2348 @@ const KNOWN_TLDS: &[&str] = &[
     "arpa", "name", "pro", "aero", "coop", "museum", "mobi", "asia", "tel", "cat", "jobs",
     "travel", "us", "ca", "de", "fr", "au", "jp", "cn", "ru", "br", "it", "es", "nl", "se", "no",
     "fi", "dk", "pl", "ch", "be", "at",
+    // Multi-label public suffixes, matched before their last label
+    "co.uk", "org.uk", "gov.uk", "ac.uk", "me.uk", "ltd.uk", "plc.uk", "net.uk", "com.tr", "net.tr",
+    "org.tr", "gov.tr", "edu.tr", "k12.tr", "com.au", "net.au", "org.au", "gov.au", "edu.au",
+    "co.jp", "ne.jp", "or.jp", "com.br", "net.br", "org.br", "gov.br", "com.cn", "net.cn",
+    "org.cn", "gov.cn", "co.nz", "org.nz",
 ];
 
-/// A layer that routes requests based on the `Host` header (subdomain).
+type ReadinessCheck = Arc<dyn Fn() -> bool + Send + Sync>;
+type RequestHook = Arc<dyn Fn(&RequestInfo) + Send + Sync>;
+/// An access-log entry emitted through
+/// [`access_log_channel`](SubdomainLayer::access_log_channel) after each request.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AccessRecord {
+    /// The subdomain extracted from the host, if any.
+    pub subdomain: Option<String>,
+    /// The request method.
+    pub method: Method,
+    /// The request path.
+    pub path: String,
+    /// The response status.
+    pub status: StatusCode,
+    /// Time taken to produce the response.
+    pub duration: Duration,
+}
+
+type ResponseFactory = Arc<dyn Fn() -> Response + Send + Sync>;
+type HostExtractor = Arc<dyn Fn(&str) -> Extraction + Send + Sync>;
+type DispatchHook = Arc<dyn Fn(&mut Request, Option<&str>) + Send + Sync>;
+type ResponseHook = Arc<dyn Fn(&mut Response, Option<&str>) + Send + Sync>;
+type InitHook = Arc<dyn Fn() -> BoxFuture<'static, Vec<(String, Router)>> + Send + Sync>;
+#[cfg(feature = "proxy")]
+type ProxyClient = hyper_util::client::legacy::Client<
+    hyper_util::client::legacy::connect::HttpConnector,
+    axum::body::Body,
+>;
+
+/// The result of a custom [`extractor`](SubdomainLayer::extractor).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extraction {
+    /// The routing key, or `None` to treat the host as the apex.
+    pub key: Option<String>,
+    /// The host downstream handlers should see in [`OriginalHost`], e.g. with an environment
+    /// label stripped.
+    pub effective_host: String,
+}
+
+/// The host a matched subdomain request arrived on, inserted as a request extension.
+///
+/// With nested layers, the outermost layer's value is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginalHost {
+    /// The `Host` header without any port.
+    pub raw: String,
+    /// The effective host reported by the custom extractor, or `raw` without one.
+    pub effective: String,
+}
+
+/// Details about a request and how it was routed, passed to the
+/// [`on_request`](SubdomainLayer::on_request) hook.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RequestInfo {
+    /// The raw `Host` header (or URI authority, under HTTP/2), including any port.
+    pub raw_host: Option<String>,
+    /// The subdomain extracted from the host, if any.
+    pub subdomain: Option<String>,
+    /// The registration key that matched, if any.
+    pub matched: Option<String>,
+    /// The request method.
+    pub method: Method,
+    /// The request path.
+    pub path: String,
+}
+
+/// The feature flags enabled for a subdomain registered with
+/// [`register_with_flags`](SubdomainLayer::register_with_flags), inserted as a request extension.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureFlags(Arc<HashSet<String>>);
+
+impl FeatureFlags {
+    /// Whether `flag` is enabled.
+    pub fn is_enabled(&self, flag: &str) -> bool {
+        self.0.contains(flag)
+    }
+
+    /// All enabled flags.
+    pub fn flags(&self) -> &HashSet<String> {
+        &self.0
+    }
+}
+
+/// The subdomain a request was routed by, inserted as a request extension for matched routers.
+///
+/// For wildcard registrations this is the actual subdomain, e.g. `tenant1` for a request matched
+/// by `*`. Matching is case-insensitive, but the subdomain keeps the case the client sent, so
+/// `API.example.com` matched by `api` carries `MatchedSubdomain("API")`. Handlers extract it directly; the extraction fails with
+/// [`MissingMatchedSubdomain`] on routes reached without a match, such as the main router, where
+/// `Option<MatchedSubdomain>` can be used instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedSubdomain(pub String);
+
+impl<S: Send + Sync> FromRequestParts<S> for MatchedSubdomain {
+    type Rejection = MissingMatchedSubdomain;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Self>()
+            .cloned()
+            .ok_or(MissingMatchedSubdomain)
+    }
+}
+
+impl<S: Send + Sync> OptionalFromRequestParts<S> for MatchedSubdomain {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        Ok(parts.extensions.get::<Self>().cloned())
+    }
+}
+
+/// The subdomains matched by enclosing layers when `SubdomainLayer`s are nested, outermost first,
+/// inserted as a request extension.
+///
+/// [`MatchedSubdomain`] holds the innermost match, so for `api.eu.example.com` routed by an
+/// outer `api.eu` registration and then by an inner layer's `api`, the handler sees
+/// `MatchedSubdomain("api")` and `OuterSubdomains(vec!["api.eu"])`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OuterSubdomains(pub Vec<String>);
+
+/// Rejection for [`MatchedSubdomain`] when the request was not routed to a subdomain router.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingMatchedSubdomain;
+
+impl fmt::Display for MissingMatchedSubdomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("request was not routed to a subdomain router")
+    }
+}
+
+impl std::error::Error for MissingMatchedSubdomain {}
+
+impl IntoResponse for MissingMatchedSubdomain {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+    }
+}
+
+/// The `Retry-After` value sent with `429` and `503` responses, configured with
+/// [`retry_after`](SubdomainLayer::retry_after).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// Retry after this many seconds, e.g. `Retry-After: 120`.
+    Seconds(u64),
+    /// Retry at this time, sent as an HTTP-date, e.g. `Retry-After: Wed, 21 Oct 2026 07:28:00 GMT`.
+    Date(SystemTime),
+}
+
+impl RetryAfter {
+    /// The header value for this delay.
+    fn header_value(&self) -> HeaderValue {
+        match self {
+            Self::Seconds(seconds) => HeaderValue::from(*seconds),
+            Self::Date(date) => HeaderValue::from_str(&httpdate::fmt_http_date(*date))
+                .expect("HTTP-dates are valid header values"),
+        }
+    }
+}
+
+/// The subdomain of a request no router matched, inserted as a request extension for the
+/// [`default_subdomain_router`](SubdomainLayer::default_subdomain_router).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmatchedSubdomain(pub String);
+
+/// The capture groups of a [`register_regex`](SubdomainLayer::register_regex) pattern, inserted
+/// as a request extension when a request is routed by it.
+///
+/// Read it with `Extension<SubdomainCaptures>`; for a subdomain `user-42` matched by
+/// `user-(?<id>\d+)`, both `get(1)` and `name("id")` return `"42"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubdomainCaptures {
+    groups: Vec<Option<String>>,
+    names: HashMap<String, usize>,
+}
+
+impl SubdomainCaptures {
+    /// The capture group at index `i`, where `0` is the whole subdomain.
+    pub fn get(&self, i: usize) -> Option<&str> {
+        self.groups.get(i)?.as_deref()
+    }
+
+    /// The named capture group `name`.
+    pub fn name(&self, name: &str) -> Option<&str> {
+        self.get(*self.names.get(name)?)
+    }
+}
+
+/// The version label stripped from a subdomain such as `v2.api`, inserted as a request extension
+/// when [`version_label_prefix`](SubdomainLayer::version_label_prefix) is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiVersion(pub String);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AccessRecord {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut record = serializer.serialize_struct("AccessRecord", 5)?;
+        record.serialize_field("subdomain", &self.subdomain)?;
+        record.serialize_field("method", self.method.as_str())?;
+        record.serialize_field("path", &self.path)?;
+        record.serialize_field("status", &self.status.as_u16())?;
+        record.serialize_field("duration", &self.duration)?;
+        record.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RequestInfo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut info = serializer.serialize_struct("RequestInfo", 5)?;
+        info.serialize_field("raw_host", &self.raw_host)?;
+        info.serialize_field("subdomain", &self.subdomain)?;
+        info.serialize_field("matched", &self.matched)?;
+        info.serialize_field("method", self.method.as_str())?;
+        info.serialize_field("path", &self.path)?;
+        info.end()
+    }
+}
+
+type RouterFactory = Box<dyn Fn() -> Router + Send + Sync>;
+
+/// The hasher of the route map: `ahash` with the `ahash` feature, the standard one otherwise.
+#[cfg(feature = "ahash")]
+type RouteHasher = ahash::RandomState;
+#[cfg(not(feature = "ahash"))]
+type RouteHasher = std::collections::hash_map::RandomState;
+
+/// A registered router, either built up front or on first match.
 #[derive(Clone)]
-pub struct SubdomainLayer {
-    routes: Arc<HashMap<String, Router>>,
+enum Route {
+    Eager(Router),
+    Lazy(Arc<LazyRoute>),
+}
+
+/// A router registered with [`register_with`](SubdomainLayer::register_with).
+struct LazyRoute {
+    build: RouterFactory,
+    router: OnceLock<Router>,
+}
+
+impl Route {
+    /// The router, building it first if it is lazy and has not been built yet.
+    fn router(&self) -> &Router {
+        match self {
+            Self::Eager(router) => router,
+            Self::Lazy(lazy) => lazy.router.get_or_init(&lazy.build),
+        }
+    }
+}
+
+/// Routing configuration shared between a `SubdomainLayer` and the services it creates.
+#[derive(Clone)]
+struct Config {
+    routes: HashMap<String, Route, RouteHasher>,
     strict: bool,
-    known_hosts: Arc<Vec<String>>,
+    known_hosts: Vec<String>,
+    tlds: Vec<String>,
     auto_detect_domain: bool,
+    alpn_header: Option<HeaderName>,
+    force_https: HashSet<String>,
+    environment_labels: Vec<String>,
+    readiness: Option<(String, ReadinessCheck)>,
+    apex_host: Option<String>,
+    catch_all: Option<Router>,
+    max_subdomain_depth: Option<usize>,
+    redirect_apex_to: Option<(String, StatusCode)>,
+    on_request: Option<RequestHook>,
+    invalid_host_status: Option<StatusCode>,
+    global_maintenance: Option<ResponseFactory>,
+    maintenance_allowlist: Vec<String>,
+    access_log: Option<mpsc::Sender<AccessRecord>>,
+    normalize_keys: bool,
+    path_tenant_fallback: bool,
+    bypass_header: Option<HeaderName>,
+    allowed_methods: HashMap<String, Vec<Method>>,
+    apex: Option<Router>,
+    path_prefix_template: Option<String>,
+    stripped_headers: HashMap<String, Vec<HeaderName>>,
+    global_preflight: Option<CorsConfig>,
+    extractor: Option<HostExtractor>,
+    strict_paths: Vec<String>,
+    error_cors: Option<CorsConfig>,
+    require_known_apex: bool,
+    ports: HashMap<String, Vec<u16>>,
+    host_apexes: HashMap<String, Router>,
+    same_origin: HashSet<String>,
+    version_label_prefix: bool,
+    exempt_paths: Vec<String>,
+    reuse_matched_subdomain: bool,
+    base_fallbacks: Vec<(String, Router)>,
+    server_timing: bool,
+    strict_response: Option<ResponseFactory>,
+    trust_forwarded_host: bool,
+    dev_mode: bool,
+    negotiated: HashMap<String, Vec<(String, Router)>>,
+    regex_routes: Vec<(String, Regex, Router)>,
+    default_subdomain_router: Option<Router>,
+    ready: Option<Arc<AtomicBool>>,
+    header_limits: HashMap<String, (usize, usize)>,
+    init_hooks: Vec<InitHook>,
+    fallthrough_paths: bool,
+    no_fallback: bool,
+    before_dispatch: Option<DispatchHook>,
+    after_dispatch: Option<ResponseHook>,
+    host_routes: HashMap<String, Router>,
+    require_tld: bool,
+    default_content_types: HashMap<String, HeaderValue>,
+    ignored_prefixes: Vec<String>,
+    retry_after: Option<RetryAfter>,
+    #[cfg(feature = "query-override")]
+    query_override_param: Option<String>,
+    #[cfg(feature = "request-id")]
+    inject_request_id: bool,
+    #[cfg(feature = "proxy")]
+    upstreams: HashMap<String, (ProxyClient, Uri)>,
 }
 
-impl SubdomainLayer {
-    /// Create a new `SubdomainLayer`.
-    pub fn new() -> Self {
-        Self {
-            routes: Arc::new(HashMap::new()),
-            strict: false,
-            known_hosts: Arc::new(Vec::new()),
-            auto_detect_domain: true,
+impl Config {
+    /// Split `host` into its extracted subdomain, the routing key derived from it and the
+    /// version label stripped on the way, if any.
+    fn split_subdomain(&self, host: &str) -> Option<(String, String, Option<ApiVersion>)> {
+        if let Some(extractor) = &self.extractor {
+            let key = extractor(host).key?;
+            let key = if self.normalize_keys {
+                normalize_key(&key)
+            } else {
+                key
+            };
+            return (!key.is_empty()).then(|| (key.clone(), key, None));
+        }
+
+        let extracted = self.extract_subdomain(host)?;
+        let (key, version) = self.routing_key(&extracted)?;
+        Some((extracted, key, version))
+    }
+
+    /// Split the subdomain of `req`, whose host is `host`, honoring any query override and a
+    /// reusable [`MatchedSubdomain`].
+    fn split_request(
+        &self,
+        req: &Request,
+        host: &str,
+    ) -> Option<(String, String, Option<ApiVersion>)> {
+        if self.reuse_matched_subdomain
+            && let Some(MatchedSubdomain(subdomain)) = req.extensions().get()
+        {
+            let subdomain = subdomain.to_ascii_lowercase();
+            return Some((subdomain.clone(), subdomain, None));
+        }
+
+        #[cfg(feature = "query-override")]
+        if let Some(subdomain) = self.query_override(req) {
+            return Some((subdomain.clone(), subdomain, None));
+        }
+        #[cfg(not(feature = "query-override"))]
+        let _ = req;
+
+        self.split_subdomain(host)
+    }
+
+    /// The subdomain forced through the query override parameter, if configured and present.
+    #[cfg(feature = "query-override")]
+    fn query_override(&self, req: &Request) -> Option<String> {
+        let param = self.query_override_param.as_deref()?;
+        req.uri()
+            .query()?
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == param)
+            .map(|(_, value)| normalize_key(value))
+            .filter(|value| !value.is_empty())
+    }
+
+    /// The request's authority including any port, preferring the first `X-Forwarded-Host`
+    /// value if trusted.
+    fn authority<'a>(&self, req: &'a Request) -> Option<&'a str> {
+        if self.trust_forwarded_host
+            && let Some(forwarded) = req
+                .headers()
+                .get("x-forwarded-host")
+                .and_then(header_str)
+                .and_then(|h| h.split(',').next())
+                .map(str::trim)
+                .filter(|h| !h.is_empty())
+        {
+            return Some(forwarded);
+        }
+        request_authority(req)
+    }
+
+    /// The request's host without any port or trailing dot in its original case, preferring the first
+    /// `X-Forwarded-Host` value if trusted.
+    fn raw_host<'a>(&self, req: &'a Request) -> Option<&'a str> {
+        self.authority(req).map(bare_host)
+    }
+
+    /// The port the request was made to, from the host it is routed on or the scheme's default.
+    fn request_port(&self, req: &Request) -> u16 {
+        let port = self
+            .authority(req)
+            .and_then(|host| split_port(host).1)
+            .and_then(|port| port.parse().ok());
+        port.unwrap_or(match request_scheme(req) {
+            "https" => 443,
+            _ => 80,
+        })
+    }
+
+    /// Whether a state-changing `req` comes from the host it is routed on, per `Origin` or else
+    /// `Referer`.
+    ///
+    /// Safe methods and requests carrying neither header pass.
+    fn is_same_origin(&self, req: &Request) -> bool {
+        if matches!(
+            *req.method(),
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+        ) {
+            return true;
+        }
+
+        let headers = req.headers();
+        let Some(source) = headers
+            .get(header::ORIGIN)
+            .or_else(|| headers.get(header::REFERER))
+        else {
+            return true;
+        };
+
+        let source = source.to_str().ok().and_then(|s| s.parse::<Uri>().ok());
+        let source = source.as_ref().and_then(Uri::authority);
+        match (source, self.authority(req)) {
+            (Some(source), Some(host)) => source.as_str().eq_ignore_ascii_case(host),
+            _ => false,
+        }
+    }
+
+    /// The request's host without any port, lowercased as DNS names are case-insensitive.
+    fn request_host(&self, req: &Request) -> Option<String> {
+        self.raw_host(req).map(normalize_host)
+    }
+
+    /// `subdomain` as the client cased it, if it spans whole labels of the request's host, e.g.
+    /// `API` for the key `api` of `www.API.example.com`.
+    fn original_case(&self, req: &Request, subdomain: &str) -> String {
+        let Some(host) = self.raw_host(req) else {
+            return subdomain.to_string();
+        };
+        let starts = std::iter::once(0).chain(host.match_indices('.').map(|(index, _)| index + 1));
+        starts
+            .filter_map(|start| {
+                let end = start + subdomain.len();
+                let ends_label = host.get(end..).is_some_and(|rest| rest.starts_with('.'));
+                host.get(start..end).filter(|_| ends_label)
+            })
+            .find(|labels| labels.eq_ignore_ascii_case(subdomain))
+            .unwrap_or(subdomain)
+            .to_string()
+    }
+
+    /// The routing key for `req`, if its host has a subdomain.
+    fn request_subdomain(&self, req: &Request) -> Option<String> {
+        let host = self.request_host(req)?;
+        self.split_request(req, &host).map(|(_, key, _)| key)
+    }
+
+    /// Compute the routing key for an extracted `subdomain`, along with the version label
+    /// stripped from it, if any.
+    fn routing_key(&self, subdomain: &str) -> Option<(String, Option<ApiVersion>)> {
+        // Drop trailing environment markers, e.g. `api.staging` -> `api`
+        let mut labels: Vec<&str> = subdomain.split('.').collect();
+        while labels
+            .last()
+            .is_some_and(|label| self.environment_labels.iter().any(|env| env == label))
+        {
+            labels.pop();
+        }
+
+        // Drop one ignored leading label, e.g. `www.api` -> `api` and `www` -> the apex
+        if labels
+            .first()
+            .is_some_and(|label| self.ignored_prefixes.iter().any(|prefix| prefix == label))
+        {
+            labels.remove(0);
+        }
+
+        // Route `v2.api` on `api`, the version is exposed as an extension
+        let mut version = None;
+        if self.version_label_prefix && labels.len() > 1 && is_version_label(labels[0]) {
+            version = Some(ApiVersion(labels.remove(0).to_string()));
+        }
+
+        (!labels.is_empty()).then(|| (labels.join("."), version))
+    }
+
+    /// Extract the subdomain portion of `host`, if any.
+    fn extract_subdomain(&self, host: &str) -> Option<String> {
+        // IPv6 literals have no subdomain
+        if host.contains(':') {
+            return None;
+        }
+
+        let host = &*collapse_dots(host);
+
+        // Try known hosts, which only match on a label boundary
+        for known in self.known_hosts.iter() {
+            if host == known {
+                return None;
+            }
+            if let Some(subdomain) = host
+                .strip_suffix(known.as_str())
+                .and_then(|rest| rest.strip_suffix('.'))
+                .filter(|subdomain| !subdomain.is_empty())
+            {
+                return Some(subdomain.to_string());
+            }
+        }
+
+        if self.auto_detect_domain {
+            let host = IP_REGEX.replace_all(host, "$1_$2_$3_$4");
+            let mut parts: Vec<&str> = host.split('.').collect();
+            let suffix_labels = self.public_suffix_labels(&host);
+            if self.require_tld && suffix_labels == 0 {
+                return None;
+            }
+            parts.truncate(parts.len().saturating_sub(suffix_labels));
+            if parts.len() > 1 {
+                return Some(parts[..parts.len() - 1].join("."));
+            }
+        }
+
+        None
+    }
+
+    /// How the registration `key` matched: `exact`, `wildcard` or `regex`.
+    #[cfg(feature = "tracing")]
+    fn match_kind(&self, key: &str) -> &'static str {
+        if self
+            .regex_routes
+            .iter()
+            .any(|(pattern, _, _)| pattern == key)
+        {
+            "regex"
+        } else if key.starts_with('*') {
+            "wildcard"
+        } else {
+            "exact"
+        }
+    }
+
+    /// Whether any router is registered, by subdomain, pattern or full host.
+    fn has_routes(&self) -> bool {
+        !self.routes.is_empty() || !self.regex_routes.is_empty() || !self.host_routes.is_empty()
+    }
+
+    /// Whether the startup gate, if any, has been opened with `mark_ready`.
+    fn is_ready(&self) -> bool {
+        self.ready
+            .as_ref()
+            .is_none_or(|ready| ready.load(Ordering::Acquire))
+    }
+
+    /// Whether `path` falls under one of the `strict_for_paths` prefixes.
+    fn is_strict_path(&self, path: &str) -> bool {
+        self.strict_paths
+            .iter()
+            .any(|prefix| is_under_prefix(path, prefix))
+    }
+
+    /// Whether `host` is one of the known hosts or the apex host, or a subdomain of one.
+    fn has_known_apex(&self, host: &str) -> bool {
+        let host = &*collapse_dots(host);
+        self.known_hosts.iter().chain(&self.apex_host).any(|apex| {
+            host.strip_suffix(apex.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+        })
+    }
+
+    /// The fallback registered for the longest base domain `host` is a subdomain of, if any.
+    fn base_fallback(&self, host: &str) -> Option<&Router> {
+        self.base_fallbacks
+            .iter()
+            .filter(|(base, _)| {
+                host.strip_suffix(base.as_str())
+                    .is_some_and(|rest| rest.ends_with('.'))
+            })
+            .max_by_key(|(base, _)| base.len())
+            .map(|(_, router)| router)
+    }
+
+    /// The effective host for `host`, as reported by the custom extractor if any.
+    fn effective_host(&self, host: &str) -> String {
+        match &self.extractor {
+            Some(extractor) => extractor(host).effective_host,
+            None => host.to_string(),
+        }
+    }
+
+    /// The number of labels in the longest configured TLD that `host` ends with, e.g. 2 for
+    /// `co.uk` in `api.example.co.uk`.
+    fn public_suffix_labels(&self, host: &str) -> usize {
+        self.tlds
+            .iter()
+            .filter(|tld| {
+                host.strip_suffix(tld.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+            })
+            .map(|tld| tld.split('.').count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The host redirects to `subdomain` should target.
+    ///
+    /// Uses the configured apex host when set, even if the request came in on another base
+    /// domain, otherwise the request's own host.
+    fn redirect_host(&self, host: &str, subdomain: &str) -> String {
+        match &self.apex_host {
+            Some(apex) => format!("{subdomain}.{apex}"),
+            None => host.to_string(),
+        }
+    }
+
+    /// The registrable domain of `host`: the known host it is under, or else its public suffix
+    /// plus one label, e.g. `example.co.uk` for `www.example.co.uk`. Hosts with neither, such
+    /// as `localhost`, are returned whole.
+    fn registrable_domain<'a>(&self, host: &'a str) -> &'a str {
+        let known = self.known_hosts.iter().find(|known| {
+            host.strip_suffix(known.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+        });
+        if let Some(known) = known {
+            return &host[host.len() - known.len()..];
+        }
+
+        let suffix_labels = self.public_suffix_labels(host);
+        if !self.auto_detect_domain || suffix_labels == 0 {
+            return host;
+        }
+        host.rmatch_indices('.')
+            .nth(suffix_labels)
+            .map_or(host, |(index, _)| &host[index + 1..])
+    }
+
+    /// Report the routing decision for `req` to the `on_request` hook, if any.
+    fn observe(&self, req: &Request, subdomain: Option<&str>, matched: Option<&str>) {
+        #[cfg(feature = "otel")]
+        if let Some(subdomain) = subdomain {
+            tracing::Span::current().record("subdomain", subdomain);
+        }
+
+        if let Some(hook) = &self.on_request {
+            hook(&RequestInfo {
+                raw_host: request_authority(req).map(str::to_string),
+                subdomain: subdomain.map(str::to_string),
+                matched: matched.map(str::to_string),
+                method: req.method().clone(),
+                path: req.uri().path().to_string(),
+            });
+        }
+    }
+
+    /// Redirect an apex request to the configured primary subdomain or host, if any.
+    fn redirect_apex(&self, req: &Request, host: &str) -> Option<Response> {
+        let (target, status) = self.redirect_apex_to.as_ref()?;
+        // A target built from the request's own host keeps its port, e.g. `localhost:3000`
+        let (target, port) = match &self.apex_host {
+            _ if target.contains('.') => (target.clone(), None),
+            Some(apex) => (format!("{target}.{apex}"), None),
+            None => (
+                format!("{target}.{}", self.registrable_domain(host)),
+                self.authority(req)
+                    .and_then(|authority| split_port(authority).1),
+            ),
+        };
+
+        // Never redirect a host to itself
+        if target.eq_ignore_ascii_case(host) {
+            return None;
+        }
+
+        let path = req.uri().path_and_query().map_or("/", |pq| pq.as_str());
+        let authority = match port {
+            Some(port) => format!("{}:{port}", ascii_host(&target)),
+            None => ascii_host(&target).into_owned(),
+        };
+        let location = format!("{}://{authority}{path}", request_scheme(req));
+        Some(redirect(*status, &location))
+    }
+
+    /// The tenant named by the first path segment of an apex request, if enabled.
+    ///
+    /// Only exact registrations are eligible, as a wildcard or pattern would claim every first
+    /// segment.
+    fn path_tenant(&self, req: &Request) -> Option<String> {
+        if !self.path_tenant_fallback {
+            return None;
+        }
+
+        let segment = req.uri().path().trim_start_matches('/').split('/').next()?;
+        let segment = normalize_key(segment);
+        let eligible = !segment.is_empty()
+            && !segment.starts_with('*')
+            && self.routes.contains_key(&segment)
+            && policy(&self.ports, &segment, &segment)
+                .is_none_or(|ports| ports.contains(&self.request_port(req)));
+        eligible.then_some(segment)
+    }
+
+    /// Find the router registered for `subdomain`.
+    ///
+    /// When an ALPN header is configured and present, a `"<protocol>:<subdomain>"` registration
+    /// takes precedence over the plain subdomain. Exact registrations win over wildcards: `tenant`
+    /// falls back to `*` and `tenant.api` to `*.api`.
+    fn route(&self, req: &Request, subdomain: &str) -> Option<(&String, &Router)> {
+        let alpn = self
+            .alpn_header
+            .as_ref()
+            .and_then(|name| req.headers().get(name))
+            .and_then(|value| value.to_str().ok());
+
+        alpn.and_then(|alpn| self.routes.get_key_value(&format!("{alpn}:{subdomain}")))
+            .or_else(|| self.routes.get_key_value(subdomain))
+            .map(|(key, route)| (key, route.router()))
+            .or_else(|| {
+                self.regex_routes
+                    .iter()
+                    .find(|(_, regex, _)| is_full_match(regex, subdomain))
+                    .map(|(pattern, _, router)| (pattern, router))
+            })
+            .or_else(|| {
+                self.routes
+                    .get_key_value(&wildcard_key(subdomain))
+                    .map(|(key, route)| (key, route.router()))
+            })
+            .map(|(key, router)| (key, self.negotiate(req, key).unwrap_or(router)))
+    }
+
+    /// The capture groups of the regex registered as `key`, matched against `subdomain`.
+    fn regex_captures(&self, key: &str, subdomain: &str) -> Option<SubdomainCaptures> {
+        let (_, regex, _) = self
+            .regex_routes
+            .iter()
+            .find(|(pattern, _, _)| pattern == key)?;
+        let captures = regex.captures(subdomain)?;
+        Some(SubdomainCaptures {
+            groups: captures
+                .iter()
+                .map(|group| group.map(|group| group.as_str().to_string()))
+                .collect(),
+            names: regex
+                .capture_names()
+                .enumerate()
+                .filter_map(|(i, name)| Some((name?.to_string(), i)))
+                .collect(),
+        })
+    }
+
+    /// The router negotiated for `key` by the request's `Accept` header, if any.
+    ///
+    /// Accepted media types are tried by descending `q` weight, in the order the client listed
+    /// them for equal weights; types weighted `q=0` are never selected.
+    fn negotiate(&self, req: &Request, key: &str) -> Option<&Router> {
+        let negotiated = self.negotiated.get(key)?;
+        let accept = req.headers().get("accept")?.to_str().ok()?;
+        let mut accepted: Vec<(&str, f32)> = accept
+            .split(',')
+            .map(|range| {
+                let mut params = range.split(';').map(str::trim);
+                let media_type = params.next().unwrap_or_default();
+                let weight = params
+                    .filter_map(|param| param.split_once('='))
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+                    .and_then(|(_, weight)| weight.trim().parse().ok())
+                    .unwrap_or(1.0);
+                (media_type, weight)
+            })
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+        // A stable sort keeps the client's order between equal weights
+        accepted.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        accepted
+            .into_iter()
+            .find_map(|(media_type, _)| {
+                negotiated
+                    .iter()
+                    .find(|(registered, _)| registered.eq_ignore_ascii_case(media_type))
+            })
+            .map(|(_, router)| router)
+    }
+
+    /// Dispatch `req` to the router matched for its `subdomain`.
+    ///
+    /// `started` is when routing began, reported through `Server-Timing` if enabled.
+    async fn dispatch_matched(
+        &self,
+        mut req: Request,
+        subdomain: &str,
+        router: Router,
+        started: Option<Instant>,
+    ) -> Result<Response, Infallible> {
+        if let Some(MatchedSubdomain(outer)) = req
+            .extensions_mut()
+            .insert(MatchedSubdomain(subdomain.to_string()))
+        {
+            // Keep the match of an enclosing layer rather than clobbering it
+            req.extensions_mut()
+                .get_or_insert_default::<OuterSubdomains>()
+                .0
+                .push(outer);
+        }
+
+        #[cfg(feature = "request-id")]
+        let request_id = self.inject_request_id.then(|| ensure_request_id(&mut req));
+
+        let routing = started.map(|started| started.elapsed());
+        if let Some(hook) = &self.before_dispatch {
+            hook(&mut req, Some(subdomain));
+        }
+        let mut response = router.oneshot(req).await?;
+        if let Some(hook) = &self.after_dispatch {
+            hook(&mut response, Some(subdomain));
+        }
+        self.add_retry_after(&mut response);
+
+        #[cfg(feature = "request-id")]
+        if let Some(request_id) = request_id {
+            response.headers_mut().insert(X_REQUEST_ID, request_id);
+        }
+        if let Some(routing) = routing {
+            let timing = format!("subdomain;dur={:.3}", routing.as_secs_f64() * 1000.0);
+            if let Ok(timing) = HeaderValue::from_str(&timing) {
+                response.headers_mut().append(SERVER_TIMING, timing);
+            }
+        }
+        Ok(response)
+    }
+
+    /// The registered subdomain closest to `input` by edit distance, if any is close enough.
+    fn suggest(&self, input: &str) -> Option<&str> {
+        let input = normalize_key(input);
+        self.routes
+            .keys()
+            .filter(|key| is_exact_key(key))
+            .map(|key| (edit_distance(&input, key), key))
+            .filter(|(distance, key)| {
+                *distance <= input.chars().count().max(key.chars().count()) / 3
+            })
+            .min_by(|(a, a_key), (b, b_key)| a.cmp(b).then_with(|| a_key.cmp(b_key)))
+            .map(|(_, key)| key.as_str())
+    }
+
+    /// The development-mode 404 for an unknown `subdomain`, listing the registered subdomains.
+    fn dev_not_found(&self, subdomain: &str) -> Response {
+        let mut known: Vec<&str> = self.routes.keys().map(String::as_str).collect();
+        known.sort_unstable();
+
+        let mut body = format!("No router is registered for subdomain `{subdomain}`.\n");
+        if let Some(suggestion) = self.suggest(subdomain) {
+            body.push_str(&format!("Did you mean `{suggestion}`?\n"));
+        }
+        body.push_str(&format!("Registered subdomains: {}\n", known.join(", ")));
+        (StatusCode::NOT_FOUND, body).into_response()
+    }
+
+    /// Finish a response generated by the layer itself, adding the error CORS headers if any.
+    fn layer_response(
+        &self,
+        req: &Request,
+        mut response: Response,
+    ) -> Result<Response, Infallible> {
+        if let Some(cors) = &self.error_cors {
+            cors.apply_origin(req, response.headers_mut());
+        }
+        self.add_retry_after(&mut response);
+        Ok(response)
+    }
+
+    /// Add the configured `Retry-After` header to a `429` or `503` response lacking one.
+    fn add_retry_after(&self, response: &mut Response) {
+        if let Some(retry_after) = &self.retry_after
+            && matches!(
+                response.status(),
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+            )
+        {
+            response
+                .headers_mut()
+                .entry(header::RETRY_AFTER)
+                .or_insert_with(|| retry_after.header_value());
+        }
+    }
+
+    /// Route `req` to the matching subdomain router, falling back to `inner`.
+    async fn dispatch<S>(&self, req: Request, inner: S) -> Result<Response, Infallible>
+    where
+        S: Service<Request, Response = Response, Error = Infallible> + Send,
+        S::Future: Send,
+    {
+        let started = self.server_timing.then(Instant::now);
+
+        if let Some(cors) = &self.global_preflight
+            && is_preflight(&req)
+        {
+            self.observe(&req, None, None);
+            return Ok(cors.preflight(&req));
+        }
+
+        if let Some(maintenance) = &self.global_maintenance
+            && !self
+                .maintenance_allowlist
+                .iter()
+                .any(|path| req.uri().path() == path)
+        {
+            self.observe(&req, None, None);
+            return self.layer_response(&req, maintenance());
+        }
+
+        if self
+            .exempt_paths
+            .iter()
+            .any(|prefix| is_under_prefix(req.uri().path(), prefix))
+        {
+            self.observe(&req, None, None);
+            return inner.oneshot(req).await;
+        }
+
+        if let Some((path, check)) = &self.readiness
+            && req.uri().path() == path
+        {
+            self.observe(&req, None, None);
+            let routed = self.has_routes() || self.catch_all.is_some();
+            let status = if routed && self.is_ready() && check() {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            return self.layer_response(&req, status.into_response());
+        }
+
+        if let Some(router) = &self.catch_all {
+            self.observe(&req, None, None);
+            return self.call_unmatched(req, router.clone()).await;
+        }
+
+        if self.bypass_header.as_ref().is_some_and(|name| {
+            req.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(is_truthy)
+        }) {
+            self.observe(&req, None, None);
+            return inner.oneshot(req).await;
+        }
+
+        if let Some(status) = self.invalid_host_status
+            && req
+                .headers()
+                .get("host")
+                .is_some_and(|h| header_str(h).is_none())
+        {
+            self.observe(&req, None, None);
+            let response = (status, INVALID_HOST_MESSAGE).into_response();
+            return self.layer_response(&req, response);
+        }
+
+        let Some(host) = self.request_host(&req) else {
+            self.observe(&req, None, None);
+            return self.fall_back(req, inner).await;
+        };
+
+        if let Some((key, router)) = self.host_routes.get_key_value(&host) {
+            self.observe(&req, None, Some(key));
+            return self.call_unmatched(req, router.clone()).await;
+        }
+
+        if self.require_known_apex && !self.has_known_apex(&host) {
+            self.observe(&req, None, None);
+            if self.strict {
+                #[cfg(feature = "tracing")]
+                trace_outcome(&req, None, "strict_not_found");
+                return self.layer_response(&req, StatusCode::NOT_FOUND.into_response());
+            }
+            return self.fall_back(req, inner).await;
+        }
+
+        // A path tenant is routed like a subdomain, its segment only stripped just before dispatch
+        let mut path_tenant = false;
+        let split = self.split_request(&req, &host).or_else(|| {
+            let tenant = self.path_tenant(&req)?;
+            path_tenant = true;
+            Some((tenant.clone(), tenant, None))
+        });
+        let Some((extracted, sub, version)) = split else {
+            self.observe(&req, None, None);
+            #[cfg(feature = "tracing")]
+            trace_outcome(&req, None, "apex");
+            if let Some(router) = self.host_apexes.get(&host) {
+                return self.call_unmatched(req, router.clone()).await;
+            }
+            if let Some(response) = self.redirect_apex(&req, &host) {
+                return self.layer_response(&req, response);
+            }
+            if let Some(router) = &self.apex {
+                return self.call_unmatched(req, router.clone()).await;
+            }
+            return self.fall_back(req, inner).await;
+        };
+
+        if !self.is_ready() {
+            self.observe(&req, Some(&sub), None);
+            let response = StatusCode::SERVICE_UNAVAILABLE.into_response();
+            return self.layer_response(&req, response);
+        }
+
+        // Per-subdomain policies apply by the registration key that matched, such as `*` or a
+        // regex pattern, as well as by the concrete subdomain
+        let matched = self.route(&req, &sub);
+        let policy_key = matched.map_or(sub.as_str(), |(key, _)| key.as_str());
+
+        if has_policy(&self.force_https, policy_key, &sub)
+            && explicit_scheme(&req).is_some_and(|scheme| scheme.eq_ignore_ascii_case("http"))
+        {
+            self.observe(&req, Some(&sub), None);
+            let path = req.uri().path_and_query().map_or("/", |pq| pq.as_str());
+            let host = if path_tenant {
+                host
+            } else {
+                self.redirect_host(&host, &extracted)
+            };
+            let location = format!("https://{}{path}", ascii_host(&host));
+            let response = redirect(StatusCode::PERMANENT_REDIRECT, &location);
+            return self.layer_response(&req, response);
+        }
+
+        if self
+            .max_subdomain_depth
+            .is_some_and(|depth| sub.split('.').count() > depth)
+        {
+            self.observe(&req, Some(&sub), None);
+            return self.layer_response(&req, StatusCode::NOT_FOUND.into_response());
+        }
+
+        if let Some((key, router)) = matched
+            && policy(&self.ports, key, &sub)
+                .is_none_or(|ports| ports.contains(&self.request_port(&req)))
+        {
+            self.observe(&req, Some(&sub), Some(key));
+            #[cfg(feature = "tracing")]
+            trace_outcome(&req, Some(&sub), self.match_kind(key));
+
+            if let Some(&(max_count, max_bytes)) = policy(&self.header_limits, key, &sub)
+                && exceeds_header_limits(&req, max_count, max_bytes)
+            {
+                let response = StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE.into_response();
+                return self.layer_response(&req, response);
+            }
+
+            if let Some(methods) = policy(&self.allowed_methods, key, &sub)
+                && !methods.contains(req.method())
+            {
+                return self.layer_response(&req, method_not_allowed(methods));
+            }
+
+            if has_policy(&self.same_origin, key, &sub) && !self.is_same_origin(&req) {
+                return self.layer_response(&req, StatusCode::FORBIDDEN.into_response());
+            }
+
+            let (mut req, retry) = if self.fallthrough_paths && !self.no_fallback {
+                match duplicate_request(req).await {
+                    Ok(requests) => requests,
+                    Err((req, response)) => return self.layer_response(&req, response),
+                }
+            } else {
+                (req, None)
+            };
+            if req.extensions().get::<OriginalHost>().is_none() {
+                let original_host = OriginalHost {
+                    effective: self.effective_host(&host),
+                    raw: host,
+                };
+                req.extensions_mut().insert(original_host);
+            }
+            if let Some(version) = version {
+                req.extensions_mut().insert(version);
+            }
+            if let Some(captures) = self.regex_captures(key, &sub) {
+                req.extensions_mut().insert(captures);
+            }
+            if let Some(names) = policy(&self.stripped_headers, key, &sub) {
+                for name in names {
+                    req.headers_mut().remove(name);
+                }
+            }
+
+            if path_tenant {
+                req = strip_first_segment(req);
+            }
+
+            let router = router.clone();
+            let matched = self.original_case(&req, &sub);
+            let mut response = self
+                .dispatch_matched(req, &matched, router, started)
+                .await?;
+            if let Some(retry) = retry
+                && response.status() == StatusCode::NOT_FOUND
+            {
+                return self.fall_back(retry, inner).await;
+            }
+            if let Some(content_type) = policy(&self.default_content_types, key, &sub) {
+                response
+                    .headers_mut()
+                    .entry(header::CONTENT_TYPE)
+                    .or_insert_with(|| content_type.clone());
+            }
+            return Ok(response);
+        }
+
+        self.observe(&req, Some(&sub), None);
+        if self.strict || self.is_strict_path(req.uri().path()) {
+            #[cfg(feature = "tracing")]
+            trace_outcome(&req, Some(&sub), "strict_not_found");
+            let response = match &self.strict_response {
+                _ if self.dev_mode => self.dev_not_found(&sub),
+                Some(strict_response) => strict_response(),
+                None => Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            };
+            return self.layer_response(&req, response);
+        }
+
+        #[cfg(feature = "tracing")]
+        trace_outcome(&req, Some(&sub), "fallback");
+        if let Some(router) = self.base_fallback(&host) {
+            return self.call_unmatched(req, router.clone()).await;
+        }
+
+        if let Some(router) = &self.default_subdomain_router {
+            let mut req = req;
+            let attempted = self.original_case(&req, &sub);
+            req.extensions_mut().insert(UnmatchedSubdomain(attempted));
+            return self.call_unmatched(req, router.clone()).await;
+        }
+
+        let req = match &self.path_prefix_template {
+            Some(template) => prefix_path(req, &template.replace("{subdomain}", &sub)),
+            None => req,
+        };
+
+        // Fallback to inner service
+        self.fall_back(req, inner).await
+    }
+
+    /// Hand an unmatched `req` to `inner`, or answer `404 Not Found` with `no_fallback`.
+    async fn fall_back<S>(&self, req: Request, inner: S) -> Result<Response, Infallible>
+    where
+        S: Service<Request, Response = Response, Error = Infallible> + Send,
+        S::Future: Send,
+    {
+        if self.no_fallback {
+            return self.layer_response(&req, StatusCode::NOT_FOUND.into_response());
+        }
+        self.call_unmatched(req, inner).await
+    }
+
+    /// Call `service` with a request no subdomain router matched, running the dispatch hooks
+    /// with `None`.
+    async fn call_unmatched<S>(&self, mut req: Request, service: S) -> Result<Response, Infallible>
+    where
+        S: Service<Request, Response = Response, Error = Infallible> + Send,
+        S::Future: Send,
+    {
+        if let Some(hook) = &self.before_dispatch {
+            hook(&mut req, None);
+        }
+        let mut response = service.oneshot(req).await?;
+        if let Some(hook) = &self.after_dispatch {
+            hook(&mut response, None);
+        }
+        Ok(response)
+    }
+}
+
+/// Whether the leftmost match of `regex` covers all of `subdomain`.
+fn is_full_match(regex: &Regex, subdomain: &str) -> bool {
+    regex
+        .find(subdomain)
+        .is_some_and(|found| found.start() == 0 && found.end() == subdomain.len())
+}
+
+/// The policy in `policies` for the registration `key` that matched, or else for `subdomain`.
+fn policy<'a, V>(policies: &'a HashMap<String, V>, key: &str, subdomain: &str) -> Option<&'a V> {
+    policies.get(key).or_else(|| policies.get(subdomain))
+}
+
+/// Whether `policies` holds the registration `key` that matched or `subdomain`.
+fn has_policy(policies: &HashSet<String>, key: &str, subdomain: &str) -> bool {
+    policies.contains(key) || policies.contains(subdomain)
+}
+
+/// The raw `Host` header, or the URI authority (the HTTP/2 `:authority`) if there is none.
+fn request_authority(req: &Request) -> Option<&str> {
+    match req.headers().get("host") {
+        Some(host) => header_str(host),
+        None => req.uri().authority().map(|authority| {
+            let authority = authority.as_str();
+            authority
+                .rsplit_once('@')
+                .map_or(authority, |(_, host)| host)
+        }),
+    }
+}
+
+/// The Levenshtein distance between `a` and `b`, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Split `host` into the host name and its `:port`, if any.
+///
+/// Bracketed IPv6 literals such as `[::1]:3000` lose their brackets, and a bare IPv6 address
+/// such as `fe80::1` is returned whole.
+fn split_port(host: &str) -> (&str, Option<&str>) {
+    if let Some(rest) = host.strip_prefix('[') {
+        return match rest.split_once(']') {
+            Some((ip, port)) => (ip, port.strip_prefix(':')),
+            None => (rest, None),
+        };
+    }
+    match host.split_once(':') {
+        Some((name, port)) if !port.contains(':') => (name, Some(port)),
+        _ => (host, None),
+    }
+}
+
+/// `host` without a trailing `:port`.
+fn strip_port(host: &str) -> &str {
+    split_port(host).0
+}
+
+/// `host` without a trailing `:port` or the trailing dot of a fully-qualified name, so
+/// `api.example.com.:8080` becomes `api.example.com`.
+fn bare_host(host: &str) -> &str {
+    let host = strip_port(host);
+    host.strip_suffix('.').unwrap_or(host)
+}
+
+/// Collapse runs of dots in `host` into one, e.g. `api.example..com` -> `api.example.com`.
+fn collapse_dots(host: &str) -> std::borrow::Cow<'_, str> {
+    if !host.contains("..") {
+        return host.into();
+    }
+
+    let mut collapsed = String::with_capacity(host.len());
+    for c in host.chars() {
+        if !(c == '.' && collapsed.ends_with('.')) {
+            collapsed.push(c);
+        }
+    }
+    collapsed.into()
+}
+
+/// Whether `path` equals `prefix` or lies beneath it, e.g. `/api/users` for `/api`.
+fn is_under_prefix(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Remove the first segment from the request path, e.g. `/acme/info` -> `/info`.
+fn strip_first_segment(mut req: Request) -> Request {
+    let path = req.uri().path().trim_start_matches('/');
+    let rest = path.find('/').map_or("", |index| &path[index..]);
+    let rest = if rest.is_empty() { "/" } else { rest };
+    let rest = rest.to_string();
+    set_path(&mut req, &rest);
+    req
+}
+
+/// Prepend `prefix` to the request path, e.g. `/t/acme` + `/info` -> `/t/acme/info`.
+fn prefix_path(mut req: Request, prefix: &str) -> Request {
+    let prefix = prefix.trim_end_matches('/');
+    let path = match req.uri().path() {
+        "/" => prefix.to_string(),
+        path => format!("{prefix}{path}"),
+    };
+    set_path(&mut req, &path);
+    req
+}
+
+/// Replace the request path, keeping the query string.
+fn set_path(req: &mut Request, path: &str) {
+    let uri = req.uri();
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    if let Ok(path_and_query) = path_and_query.parse() {
+        parts.path_and_query = Some(path_and_query);
+        if let Ok(uri) = Uri::from_parts(parts) {
+            *req.uri_mut() = uri;
+        }
+    }
+}
+
+/// Whether `label` is a version label such as `v1` or `v12`.
+fn is_version_label(label: &str) -> bool {
+    label
+        .strip_prefix('v')
+        .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// The wildcard key covering `subdomain`'s first label, e.g. `tenant.api` -> `*.api`.
+fn wildcard_key(subdomain: &str) -> String {
+    match subdomain.split_once('.') {
+        Some((_, rest)) => format!("*.{rest}"),
+        None => "*".to_string(),
+    }
+}
+
+/// Whether the registration `key` names a single subdomain, rather than a wildcard or an
+/// ALPN-qualified `"<protocol>:<subdomain>"` key.
+fn is_exact_key(key: &str) -> bool {
+    !key.contains(['*', ':'])
+}
+
+/// Whether `key` contains characters used by glob or regex patterns, other than a supported
+/// leading `*` label.
+fn looks_like_pattern(key: &str) -> bool {
+    let key = match key.strip_prefix('*') {
+        Some(rest) if rest.is_empty() || rest.starts_with('.') => rest,
+        _ => key,
+    };
+    key.contains([
+        '*', '?', '[', ']', '{', '}', '(', ')', '^', '$', '|', '+', '\\',
+    ])
+}
+
+/// The largest request body buffered so a request can fall through to the main router.
+const FALLTHROUGH_BODY_LIMIT: usize = 64 * 1024;
+
+/// Buffer `req`'s body and return it alongside a copy, if the body is known to be at most
+/// [`FALLTHROUGH_BODY_LIMIT`] bytes.
+///
+/// Other requests are returned unchanged without a copy. Fails with `400 Bad Request` and the
+/// request without its body if the body cannot be read.
+async fn duplicate_request(
+    req: Request,
+) -> Result<(Request, Option<Request>), (Request, Response)> {
+    use axum::body::{Body, HttpBody};
+
+    if req
+        .body()
+        .size_hint()
+        .upper()
+        .is_none_or(|upper| upper > FALLTHROUGH_BODY_LIMIT as u64)
+    {
+        return Ok((req, None));
+    }
+
+    let (parts, body) = req.into_parts();
+    let body = match axum::body::to_bytes(body, FALLTHROUGH_BODY_LIMIT).await {
+        Ok(body) => body,
+        Err(_) => {
+            let req = Request::from_parts(parts, Body::empty());
+            return Err((req, StatusCode::BAD_REQUEST.into_response()));
+        }
+    };
+
+    let mut copy = Request::new(Body::from(body.clone()));
+    *copy.method_mut() = parts.method.clone();
+    *copy.uri_mut() = parts.uri.clone();
+    *copy.version_mut() = parts.version;
+    *copy.headers_mut() = parts.headers.clone();
+    *copy.extensions_mut() = parts.extensions.clone();
+    Ok((Request::from_parts(parts, Body::from(body)), Some(copy)))
+}
+
+/// Whether `req` has more than `max_count` headers or more than `max_bytes` of header names and
+/// values combined.
+fn exceeds_header_limits(req: &Request, max_count: usize, max_bytes: usize) -> bool {
+    let headers = req.headers();
+    let bytes: usize = headers
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len())
+        .sum();
+    headers.len() > max_count || bytes > max_bytes
+}
+
+/// Whether a header value reads as "on", e.g. `1`, `true` or `yes`.
+fn is_truthy(value: &str) -> bool {
+    ["1", "true", "yes", "on"]
+        .iter()
+        .any(|truthy| value.trim().eq_ignore_ascii_case(truthy))
+}
+
+/// Emit the routing decision for `req` as a `DEBUG` event with stable `host`, `subdomain` and
+/// `outcome` fields.
+#[cfg(feature = "tracing")]
+fn trace_outcome(req: &Request, subdomain: Option<&str>, outcome: &'static str) {
+    tracing::debug!(
+        host = request_authority(req),
+        subdomain,
+        outcome,
+        "subdomain routing decision"
+    );
+}
+
+/// Create the span a request is dispatched in, carrying OpenTelemetry semantic attributes.
+#[cfg(feature = "otel")]
+fn dispatch_span(req: &Request) -> tracing::Span {
+    let raw_host = request_authority(req);
+    let host = raw_host.map(strip_port).map(str::to_ascii_lowercase);
+    tracing::info_span!(
+        "subdomain.dispatch",
+        "http.host" = raw_host,
+        "server.address" = host.as_deref(),
+        subdomain = tracing::field::Empty,
+    )
+}
+
+/// Normalize a subdomain key by trimming surrounding whitespace and dots and lowercasing it.
+///
+/// With the `idna` feature, punycode labels are also decoded to Unicode.
+fn normalize_key(key: &str) -> String {
+    normalize_host(key.trim().trim_matches('.'))
+}
+
+/// Lowercase `host`, decoding punycode (`xn--`) labels to Unicode with the `idna` feature.
+fn normalize_host(host: &str) -> String {
+    let host = host.to_ascii_lowercase();
+    #[cfg(feature = "idna")]
+    if host.contains("xn--") || !host.is_ascii() {
+        let (decoded, result) = idna::domain_to_unicode(&host);
+        if result.is_ok() {
+            return decoded;
+        }
+    }
+    host
+}
+
+/// `host` as it must appear in a `Location` header, encoding Unicode labels to punycode with the
+/// `idna` feature.
+fn ascii_host(host: &str) -> std::borrow::Cow<'_, str> {
+    #[cfg(feature = "idna")]
+    if !host.is_ascii()
+        && let Ok(ascii) = idna::domain_to_ascii(host)
+    {
+        return std::borrow::Cow::Owned(ascii);
+    }
+    std::borrow::Cow::Borrowed(host)
+}
+
+/// The body of [`invalid_host_status`](SubdomainLayer::invalid_host_status) responses, naming
+/// what [`header_str`] accepts.
+#[cfg(feature = "idna")]
+const INVALID_HOST_MESSAGE: &str = "invalid Host header: not valid UTF-8";
+#[cfg(not(feature = "idna"))]
+const INVALID_HOST_MESSAGE: &str = "invalid Host header: not visible ASCII";
+
+/// A header value as text, allowing UTF-8 with the `idna` feature.
+fn header_str(value: &HeaderValue) -> Option<&str> {
+    #[cfg(feature = "idna")]
+    return std::str::from_utf8(value.as_bytes()).ok();
+    #[cfg(not(feature = "idna"))]
+    value.to_str().ok()
+}
+
+/// The scheme the client used, preferring `X-Forwarded-Proto` over the request URI, or `http`
+/// if neither says.
+fn request_scheme(req: &Request) -> &str {
+    explicit_scheme(req).unwrap_or("http")
+}
+
+/// The scheme named by `X-Forwarded-Proto` or the request URI, if any.
+///
+/// Servers terminating TLS themselves usually leave both out, so a missing scheme says nothing.
+fn explicit_scheme(req: &Request) -> Option<&str> {
+    req.headers()
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(str::trim)
+        .or_else(|| req.uri().scheme_str())
+}
+
+const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+#[cfg(feature = "request-id")]
+const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Return the request's `X-Request-Id`, generating and inserting one if absent.
+#[cfg(feature = "request-id")]
+fn ensure_request_id(req: &mut Request) -> HeaderValue {
+    req.headers_mut()
+        .entry(X_REQUEST_ID)
+        .or_insert_with(|| {
+            HeaderValue::from_str(&uuid::Uuid::new_v4().to_string())
+                .expect("UUIDs are valid header values")
+        })
+        .clone()
+}
+
+/// Build a `405 Method Not Allowed` response listing the `allowed` methods.
+fn method_not_allowed(allowed: &[Method]) -> Response {
+    let allow = allowed
+        .iter()
+        .map(Method::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut response = StatusCode::METHOD_NOT_ALLOWED.into_response();
+    if let Ok(allow) = HeaderValue::from_str(&allow) {
+        response.headers_mut().insert(header::ALLOW, allow);
+    }
+    response
+}
+
+/// Headers that only apply to a single connection and must not be forwarded by a proxy.
+#[cfg(feature = "proxy")]
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Forward `req` to the upstream at `base` and relay its response.
+#[cfg(feature = "proxy")]
+async fn proxy_request(client: &ProxyClient, base: &Uri, mut req: Request) -> Response {
+    let base_path = base.path().trim_end_matches('/');
+    let path = req.uri().path_and_query().map_or("/", |pq| pq.as_str());
+    let uri = Uri::builder()
+        .scheme("http")
+        .authority(base.authority().unwrap().as_str())
+        .path_and_query(format!("{base_path}{path}"))
+        .build();
+    let Ok(uri) = uri else {
+        return StatusCode::BAD_GATEWAY.into_response();
+    };
+
+    *req.uri_mut() = uri;
+    // The client only speaks HTTP/1, whatever version the request arrived with
+    *req.version_mut() = axum::http::Version::HTTP_11;
+    let headers = req.headers_mut();
+    for name in HOP_BY_HOP_HEADERS {
+        headers.remove(*name);
+    }
+    if let Ok(host) = HeaderValue::from_str(base.authority().unwrap().as_str()) {
+        headers.insert(header::HOST, host);
+    }
+
+    match client.request(req).await {
+        Ok(response) => {
+            let mut response = response.map(axum::body::Body::new);
+            for name in HOP_BY_HOP_HEADERS {
+                response.headers_mut().remove(*name);
+            }
+            response
+        }
+        Err(_) => StatusCode::BAD_GATEWAY.into_response(),
+    }
+}
+
+/// Build a redirect response pointing at `location`.
+fn redirect(status: StatusCode, location: &str) -> Response {
+    let mut response = Response::new(axum::body::Body::empty());
+    *response.status_mut() = status;
+    if let Ok(location) = HeaderValue::from_str(location) {
+        response.headers_mut().insert(header::LOCATION, location);
+    }
+    response
+}
+
+/// A CORS policy used to answer preflight requests.
+///
+/// With no origins configured, any origin is allowed (`Access-Control-Allow-Origin: *`).
+#[derive(Debug, Clone, Default)]
+pub struct CorsConfig {
+    allow_origins: Vec<String>,
+    allow_methods: Vec<Method>,
+    allow_headers: Vec<HeaderName>,
+    max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    /// Create an empty policy allowing any origin.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow an origin, e.g. `https://app.example.com`.
+    pub fn allow_origin<S: Into<String>>(mut self, origin: S) -> Self {
+        self.allow_origins.push(origin.into());
+        self
+    }
+
+    /// Set the methods allowed for cross-origin requests.
+    pub fn allow_methods(mut self, methods: &[Method]) -> Self {
+        self.allow_methods = methods.to_vec();
+        self
+    }
+
+    /// Set the request headers allowed for cross-origin requests.
+    pub fn allow_headers(mut self, headers: &[HeaderName]) -> Self {
+        self.allow_headers = headers.to_vec();
+        self
+    }
+
+    /// Set how long browsers may cache the preflight response.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Insert `Access-Control-Allow-Origin` for the origin of `req`, if allowed.
+    fn apply_origin(&self, req: &Request, headers: &mut axum::http::HeaderMap) {
+        if self.allow_origins.is_empty() {
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_ORIGIN,
+                HeaderValue::from_static("*"),
+            );
+        } else {
+            headers.append(header::VARY, HeaderValue::from_static("origin"));
+            if let Some(origin) = req.headers().get(header::ORIGIN)
+                && self.allow_origins.iter().any(|allowed| allowed == origin)
+            {
+                headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+            }
+        }
+    }
+
+    /// Build the `204 No Content` response to a preflight request.
+    fn preflight(&self, req: &Request) -> Response {
+        let mut response = StatusCode::NO_CONTENT.into_response();
+        let headers = response.headers_mut();
+        self.apply_origin(req, headers);
+
+        let methods = self
+            .allow_methods
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Ok(methods) = HeaderValue::from_str(&methods)
+            && !methods.is_empty()
+        {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, methods);
+        }
+
+        let allow_headers = self
+            .allow_headers
+            .iter()
+            .map(HeaderName::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Ok(allow_headers) = HeaderValue::from_str(&allow_headers)
+            && !allow_headers.is_empty()
+        {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, allow_headers);
+        }
+
+        if let Some(max_age) = self.max_age {
+            headers.insert(
+                header::ACCESS_CONTROL_MAX_AGE,
+                HeaderValue::from(max_age.as_secs()),
+            );
+        }
+        response
+    }
+}
+
+/// Whether `req` is a CORS preflight request.
+fn is_preflight(req: &Request) -> bool {
+    req.method() == Method::OPTIONS
+        && req
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+}
+
+/// Errors returned when building a `SubdomainLayer` from invalid input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SubdomainError {
+    /// A subdomain key was empty after normalization.
+    EmptySubdomain,
+    /// A subdomain key looked like a glob or regex pattern, which `register` would treat as a
+    /// literal key that never matches.
+    PatternKey(String),
+}
+
+impl fmt::Display for SubdomainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptySubdomain => f.write_str("subdomain key must not be empty"),
+            Self::PatternKey(key) => write!(
+                f,
+                "subdomain key `{key}` looks like a pattern but is matched literally"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SubdomainError {}
+
+/// Build a handler that lists `layer`'s registered subdomain keys, including wildcard and ALPN
+/// keys, as a JSON array, for requests authorized with `token`.
+///
+/// Requests must carry `Authorization: Bearer <token>`, otherwise they receive
+/// `401 Unauthorized`. The token is compared in constant time. Like
+/// [`routing_debug_handler`], the list is a snapshot taken when the handler is built.
+pub fn routing_list_handler<S>(layer: &SubdomainLayer, token: &str) -> impl Handler<(), S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let mut subdomains: Vec<&str> = layer.config.routes.keys().map(String::as_str).collect();
+    subdomains.sort_unstable();
+
+    RoutingListHandler {
+        body: Arc::from(serde_json::to_string(&subdomains).unwrap()),
+        token: Arc::from(token),
+    }
+}
+
+/// The handler built by [`routing_list_handler`].
+#[derive(Clone)]
+struct RoutingListHandler {
+    body: Arc<str>,
+    token: Arc<str>,
+}
+
+impl<S> Handler<(), S> for RoutingListHandler
+where
+    S: Clone + Send + Sync + 'static,
+{
+    type Future = std::future::Ready<Response>;
+
+    fn call(self, req: Request, _state: S) -> Self::Future {
+        use subtle::ConstantTimeEq;
+
+        let authorized = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|provided| bool::from(provided.as_bytes().ct_eq(self.token.as_bytes())));
+
+        let response = if authorized {
+            (
+                [(header::CONTENT_TYPE, "application/json")],
+                self.body.to_string(),
+            )
+                .into_response()
+        } else {
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+            )
+                .into_response()
+        };
+        std::future::ready(response)
+    }
+}
+
+/// A type-erased inner service, as accepted by [`SubdomainLayer::boxed_service`].
+pub type BoxedInner = tower::util::BoxCloneSyncService<Request, Response, Infallible>;
+
+/// A layer that routes requests based on the `Host` header (subdomain).
+#[derive(Clone)]
+pub struct SubdomainLayer {
+    config: Config,
+}
+
+impl SubdomainLayer {
+    /// Create a new `SubdomainLayer`.
+    pub fn new() -> Self {
+        Self {
+            config: Config {
+                routes: HashMap::default(),
+                strict: false,
+                known_hosts: Vec::new(),
+                tlds: KNOWN_TLDS.iter().map(|tld| tld.to_string()).collect(),
+                auto_detect_domain: true,
+                alpn_header: None,
+                force_https: HashSet::new(),
+                environment_labels: Vec::new(),
+                readiness: None,
+                apex_host: None,
+                catch_all: None,
+                max_subdomain_depth: None,
+                redirect_apex_to: None,
+                on_request: None,
+                invalid_host_status: None,
+                global_maintenance: None,
+                maintenance_allowlist: Vec::new(),
+                access_log: None,
+                normalize_keys: true,
+                path_tenant_fallback: false,
+                bypass_header: None,
+                allowed_methods: HashMap::new(),
+                apex: None,
+                path_prefix_template: None,
+                stripped_headers: HashMap::new(),
+                global_preflight: None,
+                extractor: None,
+                strict_paths: Vec::new(),
+                error_cors: None,
+                require_known_apex: false,
+                ports: HashMap::new(),
+                host_apexes: HashMap::new(),
+                same_origin: HashSet::new(),
+                version_label_prefix: false,
+                exempt_paths: Vec::new(),
+                reuse_matched_subdomain: false,
+                base_fallbacks: Vec::new(),
+                server_timing: false,
+                strict_response: None,
+                trust_forwarded_host: false,
+                dev_mode: false,
+                negotiated: HashMap::new(),
+                regex_routes: Vec::new(),
+                default_subdomain_router: None,
+                ready: None,
+                header_limits: HashMap::new(),
+                init_hooks: Vec::new(),
+                fallthrough_paths: false,
+                no_fallback: false,
+                before_dispatch: None,
+                after_dispatch: None,
+                host_routes: HashMap::new(),
+                require_tld: false,
+                default_content_types: HashMap::new(),
+                ignored_prefixes: Vec::new(),
+                retry_after: None,
+                #[cfg(feature = "query-override")]
+                query_override_param: None,
+                #[cfg(feature = "request-id")]
+                inject_request_id: false,
+                #[cfg(feature = "proxy")]
+                upstreams: HashMap::new(),
+            },
+        }
+    }
+
+    /// Create a `SubdomainLayer` with a router registered for each subdomain in `routes`.
+    ///
+    /// Keys are normalized like [`register`](Self::register), and the other settings keep their
+    /// defaults, so further builder calls can be chained.
+    pub fn from_map(routes: HashMap<String, Router>) -> Self {
+        routes
+            .into_iter()
+            .fold(Self::new(), |layer, (subdomain, router)| {
+                layer.register(subdomain, router)
+            })
+    }
+
+    /// Turn a user-supplied subdomain into a registration key.
+    fn key<S: Into<String>>(&self, subdomain: S) -> String {
+        let subdomain = subdomain.into();
+        if self.config.normalize_keys {
+            normalize_key(&subdomain)
+        } else {
+            subdomain
+        }
+    }
+
+    /// Register a router for a specific subdomain.
+    ///
+    /// The `subdomain` argument is matched against the extracted subdomain from the `Host` header.
+    ///
+    /// A leading `*` label registers a wildcard: `"*"` matches any single-label subdomain and
+    /// `"*.api"` matches any single label in front of `api`, such as `tenant.api`. Exact
+    /// registrations always take precedence over wildcards. Any other pattern syntax is matched
+    /// literally; use [`try_register`](Self::try_register) to reject such keys.
+    pub fn register<S: Into<String>>(mut self, subdomain: S, router: Router) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config.routes.insert(subdomain, Route::Eager(router));
+        self
+    }
+
+    /// Add a hook that loads subdomain routers asynchronously, e.g. from a database.
+    ///
+    /// Hooks run in the order they were added when [`initialize`](Self::initialize) is awaited;
+    /// each returns `(subdomain, router)` pairs that are registered like
+    /// [`register`](Self::register).
+    pub fn on_init<F>(mut self, init: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, Vec<(String, Router)>> + Send + Sync + 'static,
+    {
+        self.config.init_hooks.push(Arc::new(init));
+        self
+    }
+
+    /// Run the [`on_init`](Self::on_init) hooks and register the routers they load.
+    ///
+    /// Await this once before applying the layer and serving, as routes are fixed once the layer
+    /// is applied. The hooks are consumed, so initializing again does nothing.
+    pub async fn initialize(mut self) -> Self {
+        for init in std::mem::take(&mut self.config.init_hooks) {
+            for (subdomain, router) in init().await {
+                self = self.register(subdomain, router);
+            }
+        }
+        self
+    }
+
+    /// Route `alias` to the router already registered for `existing`, without cloning it.
+    ///
+    /// Both keys share one registration, so a lazily built router is built only once for
+    /// either. Per-subdomain policies such as [`allowed_methods`](Self::allowed_methods) are
+    /// not copied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no router is registered for `existing`.
+    pub fn alias(mut self, existing: &str, alias: &str) -> Self {
+        let existing = self.key(existing);
+        let route = self
+            .config
+            .routes
+            .get(&existing)
+            .unwrap_or_else(|| panic!("cannot alias unregistered subdomain `{existing}`"))
+            .clone();
+        let alias = self.key(alias);
+        self.config.routes.insert(alias, route);
+        self
+    }
+
+    /// Remove the router registered for `subdomain`, if any.
+    ///
+    /// The key is normalized like [`register`](Self::register). Routers added with
+    /// [`register_negotiated`](Self::register_negotiated) and ports from
+    /// [`register_on_ports`](Self::register_on_ports) are removed too; other per-subdomain
+    /// policies are kept.
+    pub fn unregister<S: Into<String>>(mut self, subdomain: S) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config.routes.remove(&subdomain);
+        self.config.negotiated.remove(&subdomain);
+        self.config.ports.remove(&subdomain);
+        #[cfg(feature = "proxy")]
+        self.config.upstreams.remove(&subdomain);
+        self
+    }
+
+    /// Register a subdomain whose router is built by `build` on first match.
+    ///
+    /// The router is built at most once, even under concurrent requests, and shared by every
+    /// service created from this layer. Useful for large apps where constructing every
+    /// subdomain's router up front is costly.
+    pub fn register_with<S, F>(mut self, subdomain: S, build: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn() -> Router + Send + Sync + 'static,
+    {
+        let subdomain = self.key(subdomain);
+        let route = LazyRoute {
+            build: Box::new(build),
+            router: OnceLock::new(),
+        };
+        self.config
+            .routes
+            .insert(subdomain, Route::Lazy(Arc::new(route)));
+        self
+    }
+
+    /// Register a router for requests to exactly `full_host`, such as `admin.other.org`.
+    ///
+    /// Full hosts are matched before any subdomain is extracted, ignoring the port and case, so
+    /// `admin.example.com` and `admin.other.org` can be served by different routers. Other hosts
+    /// are routed by subdomain as usual.
+    pub fn register_host(mut self, full_host: &str, router: Router) -> Self {
+        let host = normalize_host(bare_host(full_host.trim()));
+        self.config.host_routes.insert(host, router);
+        self
+    }
+
+    /// Register a router for requests to exactly `host`, such as one of several known hosts.
+    ///
+    /// Lets each base domain serve its own apex content, e.g. different routers for `example.com`
+    /// and `example.net`. It takes precedence over [`redirect_apex_to`](Self::redirect_apex_to)
+    /// and the shared apex router for that host. Like [`register_host`](Self::register_host),
+    /// `host` is matched ignoring any port and case.
+    pub fn register_apex_for(mut self, host: &str, router: Router) -> Self {
+        let host = normalize_host(bare_host(host.trim()));
+        self.config.host_apexes.insert(host, router);
+        self
+    }
+
+    /// Serve unknown subdomains under `base_domain` from `router` instead of the main router.
+    ///
+    /// Lets each base domain in a multi-tenant setup have its own catch-all, e.g. separate
+    /// fallbacks for unknown subdomains of `a.com` and `b.com`. The most specific base domain wins.
+    /// Strict mode still returns a 404 response for unknown subdomains.
+    pub fn fallback_for(mut self, base_domain: &str, router: Router) -> Self {
+        let base_domain = normalize_host(base_domain.trim_matches('.'));
+        self.config.base_fallbacks.push((base_domain, router));
+        self
+    }
+
+    /// Register a router for a subdomain whose requests carry the given feature flags.
+    ///
+    /// Handlers read them through the [`FeatureFlags`] extension, e.g.
+    /// `Extension(flags): Extension<FeatureFlags>`.
+    pub fn register_with_flags<S: Into<String>>(
+        self,
+        subdomain: S,
+        router: Router,
+        flags: HashSet<String>,
+    ) -> Self {
+        let flags = FeatureFlags(Arc::new(flags));
+        self.register(subdomain, router.layer(axum::Extension(flags)))
+    }
+
+    /// Never fall back to the inner service: unmatched requests get `404 Not Found`.
+    ///
+    /// Useful for a pure subdomain router. Unlike [`strict`](Self::strict), this also covers
+    /// requests without a subdomain when no apex router is registered, and requests without a
+    /// host. Configured fallbacks such as [`fallback_for`](Self::fallback_for) still apply, and
+    /// [`exempt_paths`](Self::exempt_paths) and the bypass header still reach the inner service.
+    pub fn no_fallback(mut self) -> Self {
+        self.config.no_fallback = true;
+        self
+    }
+
+    /// Retry requests against the main router when the matched subdomain router answers
+    /// `404 Not Found`.
+    ///
+    /// This lets a subdomain serve paths it has no route for, such as shared assets, from the
+    /// main router. The request body must be buffered for the retry, so only requests whose body
+    /// is known to be at most 64 KiB fall through; larger or streaming bodies get the
+    /// subdomain's `404`. The retry is handled like any fallback, so
+    /// [`no_fallback`](Self::no_fallback) disables it and the dispatch hooks see no subdomain.
+    pub fn fallthrough_paths(mut self, enabled: bool) -> Self {
+        self.config.fallthrough_paths = enabled;
+        self
+    }
+
+    /// Serve requests for unregistered subdomains with `router` instead of the main router.
+    ///
+    /// Useful for a "tenant not found" page: the attempted subdomain is available to its
+    /// handlers as an [`UnmatchedSubdomain`] extension. The main router then only serves
+    /// requests without a subdomain. Strict mode and [`fallback_for`](Self::fallback_for) take
+    /// precedence.
+    pub fn default_subdomain_router(mut self, router: Router) -> Self {
+        self.config.default_subdomain_router = Some(router);
+        self
+    }
+
+    /// Register a router for every subdomain fully matching `regex`, e.g. `user-\d+`.
+    ///
+    /// Patterns are tried in registration order when no exact registration matches, before
+    /// wildcards. The leftmost match must cover the whole (lowercased) subdomain, and its capture
+    /// groups are exposed to handlers as a [`SubdomainCaptures`] extension. `regex` is used as
+    /// built, so `RegexBuilder` options are kept; write alternations whose first branch may match
+    /// a prefix as `^(?:a|ab)$`.
+    pub fn register_regex(mut self, regex: Regex, router: Router) -> Self {
+        let pattern = regex.as_str().to_string();
+        self.config.regex_routes.push((pattern, regex, router));
+        self
+    }
+
+    /// Register `router` for requests to `subdomain` that accept `media_type`.
+    ///
+    /// Requests whose `Accept` header lists none of the negotiated media types are handled by
+    /// the router registered for `subdomain` with [`register`](Self::register), which must be
+    /// present for any negotiated router to match. Listed media types are tried by their `q`
+    /// weight, and those weighted `q=0` are never selected.
+    pub fn register_negotiated<S: Into<String>>(
+        mut self,
+        subdomain: S,
+        media_type: &str,
+        router: Router,
+    ) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config
+            .negotiated
+            .entry(subdomain)
+            .or_default()
+            .push((media_type.trim().to_string(), router));
+        self
+    }
+
+    /// Register a router for a subdomain wrapped in a subdomain-specific tower `layer`, such as
+    /// authentication or rate limiting.
+    ///
+    /// `layer` has the same bounds as [`Router::layer`] and wraps every route of `router`,
+    /// including its fallback, so it only runs for requests matched to `subdomain`.
+    pub fn register_with_layer<S, L>(self, subdomain: S, router: Router, layer: L) -> Self
+    where
+        S: Into<String>,
+        L: Layer<axum::routing::Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.register(subdomain, router.layer(layer))
+    }
+
+    /// Register a router for a subdomain whose responses carry `value` as their `Server` header.
+    ///
+    /// Any `Server` header set by the router is overridden.
+    pub fn register_with_server_header<S: Into<String>>(
+        self,
+        subdomain: S,
+        value: HeaderValue,
+        router: Router,
+    ) -> Self {
+        let server = tower::util::MapResponseLayer::new(move |mut response: Response| {
+            response.headers_mut().insert(header::SERVER, value.clone());
+            response
+        });
+        self.register(subdomain, router.layer(server))
+    }
+
+    /// Register a router for a subdomain that only matches requests on the given ports.
+    ///
+    /// The port is read from the host the request is routed on (the `Host` header, or a trusted
+    /// `X-Forwarded-Host`), defaulting to 443 for HTTPS and 80 otherwise.
+    /// Requests on other ports are treated as unmatched and fall back to the main router.
+    pub fn register_on_ports<S: Into<String>>(
+        mut self,
+        subdomain: S,
+        ports: &[u16],
+        router: Router,
+    ) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config.ports.insert(subdomain.clone(), ports.to_vec());
+        self.register(subdomain, router)
+    }
+
+    /// Register a router for a specific subdomain, rejecting keys that cannot match.
+    ///
+    /// Returns [`SubdomainError::EmptySubdomain`] for an empty key and
+    /// [`SubdomainError::PatternKey`] for keys containing glob or regex characters such as `?`,
+    /// `[` or a `*` anywhere but as the whole first label.
+    pub fn try_register<S: Into<String>>(
+        self,
+        subdomain: S,
+        router: Router,
+    ) -> Result<Self, SubdomainError> {
+        let subdomain = self.key(subdomain);
+        if subdomain.is_empty() {
+            return Err(SubdomainError::EmptySubdomain);
+        }
+        if looks_like_pattern(&subdomain) {
+            return Err(SubdomainError::PatternKey(subdomain));
+        }
+        Ok(self.register(subdomain, router))
+    }
+
+    /// Enable or disable normalization of subdomain keys passed to later builder calls.
+    ///
+    /// Normalization trims surrounding whitespace and dots and lowercases the key, and is enabled
+    /// by default. Disabling it skips that work for large route sets whose keys are already
+    /// normalized; keys with uppercase letters then never match, as hosts are lowercased.
+    pub fn normalize_keys(mut self, enable: bool) -> Self {
+        self.config.normalize_keys = enable;
+        self
+    }
+
+    /// The number of registered subdomains.
+    ///
+    /// Only exact registrations are counted, not wildcards such as `*.api` or
+    /// [ALPN](Self::alpn_header) keys such as `h2:api`.
+    pub fn len(&self) -> usize {
+        self.subdomains().len()
+    }
+
+    /// Whether no subdomains are registered, counted like [`len`](Self::len).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The registered subdomain keys, in no particular order.
+    ///
+    /// Like [`len`](Self::len), only exact registrations are listed.
+    pub fn subdomains(&self) -> Vec<&str> {
+        self.config
+            .routes
+            .keys()
+            .map(String::as_str)
+            .filter(|key| is_exact_key(key))
+            .collect()
+    }
+
+    /// Whether a router is registered for `subdomain`, normalized like a registration key.
+    ///
+    /// Only exact registrations are checked; a subdomain covered by a wildcard is not reported.
+    pub fn contains(&self, subdomain: &str) -> bool {
+        self.config.routes.contains_key(&self.key(subdomain))
+    }
+
+    /// The registered subdomain closest to `input` by edit distance, if any is similar enough.
+    ///
+    /// A suggestion is only made within one edit per three characters, so `admn` suggests
+    /// `admin` while unrelated input returns `None`. Wildcard registrations are never suggested.
+    pub fn suggest(&self, input: &str) -> Option<String> {
+        self.config.suggest(input).map(str::to_owned)
+    }
+
+    /// Register `router` for the apex: requests whose host has no subdomain, such as
+    /// `example.com`.
+    ///
+    /// Apex requests then no longer reach the inner service, which keeps serving unmatched
+    /// subdomains. [`register_apex_for`](Self::register_apex_for) routers take precedence for
+    /// their hosts.
+    pub fn apex_router(mut self, router: Router) -> Self {
+        self.config.apex = Some(router);
+        self
+    }
+
+    /// Register `router` for both the apex (requests without a subdomain) and `subdomain`.
+    ///
+    /// Both registrations share the same router, so e.g. `example.com` and `app.example.com`
+    /// serve identical routes. Apex requests no longer reach the inner service.
+    pub fn register_apex_and_subdomain<S: Into<String>>(
+        mut self,
+        subdomain: S,
+        router: Router,
+    ) -> Self {
+        self.config.apex = Some(router.clone());
+        self.register(subdomain, router)
+    }
+
+    /// Register a static site for every immediate subdirectory of `root`.
+    ///
+    /// Each subdirectory name becomes a subdomain served from that directory, so
+    /// `root/docs/index.html` is served at `docs.example.com/index.html`.
+    #[cfg(feature = "static-dir")]
+    pub fn register_static_dir_tree<P: AsRef<std::path::Path>>(
+        mut self,
+        root: P,
+    ) -> std::io::Result<Self> {
+        for entry in std::fs::read_dir(root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                let site = tower_http::services::ServeDir::new(entry.path());
+                self = self.register(name, Router::new().fallback_service(site));
+            }
         }
+        Ok(self)
     }
 
-    /// Register a router for a specific subdomain.
+    /// Forward requests for a subdomain to an upstream HTTP server.
     ///
-    /// The `subdomain` argument is matched against the extracted subdomain from the `Host` header.
-    pub fn register<S: ToString>(mut self, subdomain: S, router: Router) -> Self {
-        let mut routes = (*self.routes).clone();
-        routes.insert(subdomain.to_string(), router);
-        self.routes = Arc::new(routes);
-        self
+    /// `upstream` is a base URL such as `http://127.0.0.1:4000` or `http://backend/app`; the
+    /// request path and query are appended to it. Request and response bodies are streamed, headers
+    /// are copied except for hop-by-hop ones, and `Host` is set to the upstream authority. Upstream
+    /// failures are answered with `502 Bad Gateway`. Only plain `http://` upstreams are supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `upstream` is not an absolute `http://` URL.
+    #[cfg(feature = "proxy")]
+    pub fn register_proxy<S: Into<String>>(mut self, subdomain: S, upstream: &str) -> Self {
+        use hyper_util::{client::legacy::Client, rt::TokioExecutor};
+
+        let base: Uri = upstream
+            .parse()
+            .unwrap_or_else(|err| panic!("invalid upstream URL `{upstream}`: {err}"));
+        assert!(
+            base.scheme_str() == Some("http") && base.authority().is_some(),
+            "upstream URL `{upstream}` must be an absolute http:// URL"
+        );
+
+        let client = Client::builder(TokioExecutor::new()).build_http();
+        let subdomain = self.key(subdomain);
+        self.config
+            .upstreams
+            .insert(subdomain.clone(), (client.clone(), base.clone()));
+        let proxy = tower::service_fn(move |req: Request| {
+            let client = client.clone();
+            let base = base.clone();
+            async move { Ok::<_, Infallible>(proxy_request(&client, &base, req).await) }
+        });
+        self.register(subdomain, Router::new().fallback_service(proxy))
+    }
+
+    /// Open an idle pooled connection to every [`register_proxy`](Self::register_proxy) upstream.
+    ///
+    /// Each upstream's base URL is sent a `HEAD` request, leaving its connection in the pool
+    /// shared with the services created from this layer, so the first proxied request does not
+    /// pay for connecting. Returns the number of upstreams that answered; unreachable upstreams
+    /// are skipped and still answer proxied requests with `502 Bad Gateway`.
+    #[cfg(feature = "proxy")]
+    pub async fn warm_upstreams(&self) -> usize {
+        let warm = self
+            .config
+            .upstreams
+            .values()
+            .map(|(client, base)| async move {
+                let req = Request::builder()
+                    .method(Method::HEAD)
+                    .uri(base.clone())
+                    .header(header::HOST, base.authority().unwrap().as_str())
+                    .body(axum::body::Body::empty())
+                    .ok()?;
+                client.request(req).await.ok()
+            });
+        futures::future::join_all(warm)
+            .await
+            .into_iter()
+            .flatten()
+            .count()
     }
 
     /// Enable or disable strict subdomain checking.
@@ -58,15 +2382,111 @@ impl SubdomainLayer {
     /// When strict checking is enabled, requests to unknown subdomains will return a 404 response
     /// instead of falling back to the main router.
     pub fn strict(mut self, strict: bool) -> Self {
-        self.strict = strict;
+        self.config.strict = strict;
+        self
+    }
+
+    /// Explain strict-mode misses with a plain-text 404 listing the registered subdomains.
+    ///
+    /// The body suggests the closest registered subdomain, if any, and takes precedence over
+    /// [`strict_response`](Self::strict_response). Off by default, as it reveals the routing
+    /// configuration; do not enable it in production.
+    pub fn dev_mode(mut self, enabled: bool) -> Self {
+        self.config.dev_mode = enabled;
+        self
+    }
+
+    /// Route on the `X-Forwarded-Host` header instead of `Host` when it is present.
+    ///
+    /// Enable this only behind a proxy that sets the header, as clients can otherwise pick any
+    /// host. If the header lists several hosts, the first is used.
+    pub fn trust_forwarded_host(mut self, trust: bool) -> Self {
+        self.config.trust_forwarded_host = trust;
+        self
+    }
+
+    /// Build the response returned for unknown subdomains in strict mode with `response_factory`.
+    ///
+    /// Replaces the default empty `404 Not Found`, e.g. with a branded HTML page or a JSON error.
+    /// Also applies to [`strict_for_paths`](Self::strict_for_paths).
+    pub fn strict_response<F>(mut self, response_factory: F) -> Self
+    where
+        F: Fn() -> Response + Send + Sync + 'static,
+    {
+        self.config.strict_response = Some(Arc::new(response_factory));
+        self
+    }
+
+    /// Apply strict subdomain checking only to paths under the given prefixes.
+    ///
+    /// Requests to unknown subdomains whose path equals a prefix or lies beneath it (e.g.
+    /// `/api/users` for `/api`) return a 404 response; other paths fall back to the main router.
+    /// Has no additional effect while [`strict`](Self::strict) is enabled.
+    pub fn strict_for_paths(mut self, prefixes: Vec<String>) -> Self {
+        self.config.strict_paths = prefixes;
+        self
+    }
+
+    /// Send requests for paths under the given prefixes straight to the main router.
+    ///
+    /// Matching paths, e.g. `/metrics` or `/favicon.ico`, skip subdomain extraction entirely and
+    /// are served by the main router on every host.
+    pub fn exempt_paths(mut self, prefixes: Vec<String>) -> Self {
+        self.config.exempt_paths = prefixes;
+        self
+    }
+
+    /// Reuse the [`MatchedSubdomain`] set by an outer layer instead of parsing the host again.
+    ///
+    /// Useful when layers are stacked, e.g. a layer inside a router registered on another layer:
+    /// the inner layer then routes on the subdomain the outer one matched, skipping extraction.
+    pub fn reuse_matched_subdomain(mut self, enable: bool) -> Self {
+        self.config.reuse_matched_subdomain = enable;
         self
     }
 
     /// Set a list of known hosts.
     ///
     /// If the host ends with one of these known hosts, the suffix is removed to extract the subdomain.
+    /// Hosts are compared case-insensitively.
     pub fn known_hosts(mut self, hosts: Vec<String>) -> Self {
-        self.known_hosts = Arc::new(hosts);
+        self.config.known_hosts = hosts
+            .into_iter()
+            .map(|host| normalize_host(&host))
+            .collect();
+        self
+    }
+
+    /// Clear the list of known hosts, so only automatic domain detection is used.
+    pub fn without_known_hosts(mut self) -> Self {
+        self.config.known_hosts.clear();
+        self
+    }
+
+    /// Replace the list of TLDs stripped by automatic domain detection.
+    ///
+    /// Entries may span several labels, such as `co.uk`; the longest entry a host ends with is
+    /// stripped, so `api.example.co.uk` yields the subdomain `api`.
+    pub fn tlds(mut self, tlds: Vec<String>) -> Self {
+        self.config.tlds = tlds.iter().map(|tld| normalize_key(tld)).collect();
+        self
+    }
+
+    /// Add entries to the list of TLDs stripped by automatic domain detection.
+    ///
+    /// See [`tlds`](Self::tlds) for how entries are matched.
+    pub fn add_tlds(mut self, tlds: Vec<String>) -> Self {
+        self.config
+            .tlds
+            .extend(tlds.iter().map(|tld| normalize_key(tld)));
+        self
+    }
+
+    /// Clear the list of TLDs stripped by automatic domain detection.
+    ///
+    /// With an empty list, the last label of the host is treated as the domain itself.
+    pub fn without_tlds(mut self) -> Self {
+        self.config.tlds.clear();
         self
     }
 
@@ -74,9 +2494,459 @@ impl SubdomainLayer {
     ///
     /// When enabled, the layer will attempt to automatically detect and strip known TLDs.
     pub fn auto_detect_domain(mut self, enable: bool) -> Self {
-        self.auto_detect_domain = enable;
+        self.config.auto_detect_domain = enable;
+        self
+    }
+
+    /// Set labels that mark a deployment environment rather than a subdomain.
+    ///
+    /// Matching labels at the end of the extracted subdomain are removed before lookup, so with
+    /// `vec!["staging".to_string()]` both `api.staging.example.com` and `api.example.com` route to
+    /// `api`.
+    pub fn environment_labels(mut self, labels: Vec<String>) -> Self {
+        self.config.environment_labels = labels.iter().map(|label| normalize_key(label)).collect();
+        self
+    }
+
+    /// Strip a leading label such as `www` from the extracted subdomain before lookup.
+    ///
+    /// With `vec!["www".to_string()]`, `www.example.com` routes like the apex and
+    /// `www.api.example.com` routes to `api`. Only a whole first label is removed, so `wwwapi`
+    /// is unaffected.
+    pub fn ignore_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.config.ignored_prefixes = prefixes
+            .iter()
+            .map(|prefix| normalize_key(prefix))
+            .collect();
+        self
+    }
+
+    /// Serve a readiness probe at `path` on every host, even while a
+    /// [`catch_all`](Self::catch_all) router is set.
+    ///
+    /// The probe answers `200 OK` when at least one router is registered (by subdomain,
+    /// [pattern](Self::register_regex), [full host](Self::register_host) or as the catch-all)
+    /// and `check` returns `true`, and `503 Service Unavailable` otherwise.
+    pub fn readiness<P, F>(mut self, path: P, check: F) -> Self
+    where
+        P: Into<String>,
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.config.readiness = Some((path.into(), Arc::new(check)));
+        self
+    }
+
+    /// Answer requests for subdomains with `503 Service Unavailable` until [`mark_ready`] is
+    /// called.
+    ///
+    /// Requests without a subdomain are served as usual, and the [`readiness`](Self::readiness)
+    /// probe reports `503` until then. The gate is shared by every clone of this layer and the
+    /// services created from it, so keep a clone to open it once startup has finished.
+    ///
+    /// [`mark_ready`]: Self::mark_ready
+    pub fn gate_until_ready(mut self) -> Self {
+        self.config.ready = Some(Arc::new(AtomicBool::new(false)));
+        self
+    }
+
+    /// Open the startup gate set up by [`gate_until_ready`](Self::gate_until_ready).
+    ///
+    /// Does nothing if the layer has no gate.
+    pub fn mark_ready(&self) {
+        if let Some(ready) = &self.config.ready {
+            ready.store(true, Ordering::Release);
+        }
+    }
+
+    /// Treat a leading version label such as `v1` or `v2` as an API version.
+    ///
+    /// When enabled, `v2.api.example.com` routes to the `api` router and matched requests carry an
+    /// [`ApiVersion`] extension holding `"v2"`. A lone version label is routed as-is.
+    pub fn version_label_prefix(mut self, enable: bool) -> Self {
+        self.config.version_label_prefix = enable;
+        self
+    }
+
+    /// Only extract a subdomain when the host ends in a known TLD or one of the
+    /// [`known_hosts`](Self::known_hosts).
+    ///
+    /// By default `api.myhost` yields the subdomain `api` even though `myhost` has no TLD; with
+    /// this enabled such hosts have no subdomain and are treated as the apex, as are IP
+    /// addresses.
+    pub fn require_tld(mut self, require: bool) -> Self {
+        self.config.require_tld = require;
+        self
+    }
+
+    /// Only route hosts whose apex is one of the [`known_hosts`](Self::known_hosts) or the
+    /// [`apex_host`](Self::apex_host).
+    ///
+    /// Other hosts, such as `api.otherdomain.com`, are not matched even if a subdomain could be
+    /// extracted: they return a 404 response in strict mode and fall back to the main router
+    /// otherwise.
+    pub fn require_known_apex(mut self, enable: bool) -> Self {
+        self.config.require_known_apex = enable;
+        self
+    }
+
+    /// Set the apex host that redirect helpers such as [`force_https`](Self::force_https) target.
+    ///
+    /// Redirects then always name this apex, so with several known hosts a request to
+    /// `admin.example.net` is sent to `admin.<apex>`; leave it unset to keep each request's own
+    /// base domain. When unset, `force_https` keeps the host the request was made to and
+    /// [`redirect_apex_to`](Self::redirect_apex_to) resolves against the request's registrable
+    /// domain.
+    pub fn apex_host(mut self, host: &str) -> Self {
+        self.config.apex_host = Some(normalize_host(host.trim_end_matches('.')));
+        self
+    }
+
+    /// Send every request to `router`, bypassing subdomain matching entirely.
+    ///
+    /// Intended as a temporary switch, e.g. during migrations or incident response.
+    pub fn catch_all(mut self, router: Router) -> Self {
+        self.config.catch_all = Some(router);
+        self
+    }
+
+    /// Limit how many labels a subdomain may have.
+    ///
+    /// Hosts with deeper subdomains are rejected with a 404 without attempting a lookup, so
+    /// `max_subdomain_depth(1)` serves `a.example.com` but rejects `a.b.example.com`.
+    pub fn max_subdomain_depth(mut self, depth: usize) -> Self {
+        self.config.max_subdomain_depth = Some(depth);
+        self
+    }
+
+    /// Redirect apex requests to a primary subdomain or host.
+    ///
+    /// `target` is either a subdomain such as `"app"`, resolved against the
+    /// [`apex_host`](Self::apex_host) or the request's registrable domain, or a full host such as
+    /// `"app.example.com"`. The path and query are preserved, and a request already on the target
+    /// host is not redirected.
+    pub fn redirect_apex_to<T: Into<String>>(mut self, target: T, status: StatusCode) -> Self {
+        self.config.redirect_apex_to = Some((target.into(), status));
+        self
+    }
+
+    /// Replace the built-in subdomain extraction with a custom `extractor`.
+    ///
+    /// `extractor` receives the host without its port and returns the routing key along with the
+    /// effective host exposed to matched routers through the [`OriginalHost`] extension. It may be
+    /// called more than once per request, so it should be cheap and deterministic. Known hosts,
+    /// TLD detection and environment labels are bypassed while an extractor is set.
+    pub fn extractor<F>(mut self, extractor: F) -> Self
+    where
+        F: Fn(&str) -> Extraction + Send + Sync + 'static,
+    {
+        self.config.extractor = Some(Arc::new(extractor));
+        self
+    }
+
+    /// Enable or disable a `Server-Timing` header on matched responses.
+    ///
+    /// The header reports the time spent parsing the host and matching a router before the
+    /// subdomain's router is called, in milliseconds, e.g. `Server-Timing: subdomain;dur=0.012`.
+    pub fn server_timing(mut self, enable: bool) -> Self {
+        self.config.server_timing = enable;
+        self
+    }
+
+    /// Call `hook` with the routing details of every request the layer handles.
+    ///
+    /// Requests answered before the host is parsed, such as preflights, maintenance responses,
+    /// readiness probes and exempt or bypassed paths, are reported without a subdomain.
+    pub fn on_request<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RequestInfo) + Send + Sync + 'static,
+    {
+        self.config.on_request = Some(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` with mutable access to each request just before it is dispatched.
+    ///
+    /// The hook runs right before the matched router is called, with the matched subdomain, and
+    /// with `None` before any other router or the inner service is: the apex, host, fallback,
+    /// default subdomain and catch-all routers included. It can add headers, rewrite the URI or
+    /// insert extensions.
+    pub fn before_dispatch<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut Request, Option<&str>) + Send + Sync + 'static,
+    {
+        self.config.before_dispatch = Some(Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` with mutable access to each response of a dispatched request.
+    ///
+    /// The counterpart of [`before_dispatch`](Self::before_dispatch): it runs on the matched
+    /// router's response, with the matched subdomain, and with `None` on the response of any
+    /// other router or the inner service. Responses generated by the layer itself, such as
+    /// strict-mode 404s, are not passed to it.
+    pub fn after_dispatch<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut Response, Option<&str>) + Send + Sync + 'static,
+    {
+        self.config.after_dispatch = Some(Arc::new(hook));
+        self
+    }
+
+    /// Respond with `status` when the `Host` header is not visible ASCII, or with the `idna`
+    /// feature, not valid UTF-8.
+    ///
+    /// By default such requests are treated like requests without a `Host` header and fall back
+    /// to the inner service.
+    pub fn invalid_host_status(mut self, status: StatusCode) -> Self {
+        self.config.invalid_host_status = Some(status);
+        self
+    }
+
+    /// Put every host into maintenance mode.
+    ///
+    /// While `enabled`, every request receives the response built by `response`, except for
+    /// paths listed with [`maintenance_allowlist`](Self::maintenance_allowlist).
+    pub fn global_maintenance<F>(mut self, enabled: bool, response: F) -> Self
+    where
+        F: Fn() -> Response + Send + Sync + 'static,
+    {
+        self.config.global_maintenance = enabled.then(|| Arc::new(response) as ResponseFactory);
+        self
+    }
+
+    /// Send `Retry-After: retry_after` with `429 Too Many Requests` and `503 Service
+    /// Unavailable` responses.
+    ///
+    /// Applies to the layer's own responses, such as maintenance and
+    /// [`gate_until_ready`](Self::gate_until_ready) `503`s, and to those of matched subdomain
+    /// routers, e.g. from a rate limiter added with
+    /// [`register_with_layer`](Self::register_with_layer). An existing `Retry-After` header is
+    /// kept.
+    pub fn retry_after(mut self, retry_after: RetryAfter) -> Self {
+        self.config.retry_after = Some(retry_after);
+        self
+    }
+
+    /// Set paths that keep being routed normally during global maintenance, e.g. `/healthz`.
+    pub fn maintenance_allowlist(mut self, paths: Vec<String>) -> Self {
+        self.config.maintenance_allowlist = paths;
+        self
+    }
+
+    /// Emit an [`AccessRecord`] for every request on a bounded channel.
+    ///
+    /// Returns the layer together with the receiving end, which is typically drained by a
+    /// background task. Records are dropped rather than delaying responses when the channel is
+    /// full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn access_log_channel(mut self, capacity: usize) -> (Self, mpsc::Receiver<AccessRecord>) {
+        assert!(
+            capacity > 0,
+            "access log channel capacity must be at least 1"
+        );
+        let (sender, receiver) = mpsc::channel(capacity);
+        self.config.access_log = Some(sender);
+        (self, receiver)
+    }
+
+    /// Let the query parameter `name` override the subdomain, e.g. `?__subdomain=api`.
+    ///
+    /// Meant for local development without DNS; never enable it in production.
+    #[cfg(feature = "query-override")]
+    pub fn query_override_param<N: Into<String>>(mut self, name: N) -> Self {
+        self.config.query_override_param = Some(name.into());
+        self
+    }
+
+    /// Enable or disable routing apex requests on their first path segment.
+    ///
+    /// When enabled and the host has no subdomain, a first path segment matching a registered
+    /// subdomain selects that router and is stripped before dispatch, so `example.com/acme/info`
+    /// is served as `/info` by the router registered for `acme`, with the same per-subdomain
+    /// policies as `acme.example.com/info`. Segments match case-insensitively, and only exact
+    /// registrations are selected this way, never wildcards or patterns.
+    pub fn path_tenant_fallback(mut self, enable: bool) -> Self {
+        self.config.path_tenant_fallback = enable;
+        self
+    }
+
+    /// Skip subdomain routing for requests carrying a truthy `name` header.
+    ///
+    /// Values such as `1`, `true`, `yes` or `on` send the request straight to the inner service,
+    /// whatever its host.
+    pub fn bypass_header(mut self, name: HeaderName) -> Self {
+        self.config.bypass_header = Some(name);
+        self
+    }
+
+    /// Prefix the path of unmatched subdomain requests before they fall back to the inner service.
+    ///
+    /// `template` must contain `{subdomain}`, which is replaced by the extracted subdomain. With
+    /// `"/t/{subdomain}"`, a request to `acme.example.com/info` reaches the inner service as
+    /// `/t/acme/info`, letting the main router serve tenants through nested routes.
+    ///
+    /// The rewrite only affects routing when the layer wraps the main router as a whole (e.g.
+    /// `layer.layer(main_router)`), since `Router::layer` runs after a route has been selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `template` does not contain `{subdomain}` or does not start with `/`.
+    pub fn path_prefix_template(mut self, template: &str) -> Self {
+        assert!(
+            template.contains("{subdomain}"),
+            "path prefix template `{template}` must contain `{{subdomain}}`"
+        );
+        assert!(
+            template.starts_with('/'),
+            "path prefix template `{template}` must start with `/`"
+        );
+        self.config.path_prefix_template = Some(template.to_string());
+        self
+    }
+
+    /// Answer CORS preflight requests on every host with a shared policy.
+    ///
+    /// `OPTIONS` requests carrying `Access-Control-Request-Method` receive `204 No Content` with
+    /// the policy's CORS headers before any routing takes place.
+    pub fn global_preflight(mut self, cors: CorsConfig) -> Self {
+        self.config.global_preflight = Some(cors);
+        self
+    }
+
+    /// Add the CORS headers of `cors` to responses generated by the layer itself.
+    ///
+    /// Covers strict and depth 404s, `405` responses, redirects, maintenance and readiness
+    /// responses, so browsers report the real status instead of a CORS failure. Responses from
+    /// registered routers are left untouched.
+    pub fn error_cors(mut self, cors: CorsConfig) -> Self {
+        self.config.error_cors = Some(cors);
+        self
+    }
+
+    /// Set the header carrying the TLS ALPN protocol negotiated at the edge (e.g. `h2`).
+    ///
+    /// When the header is present, a router registered as `"<protocol>:<subdomain>"` (such as
+    /// `"h2:api"`) is preferred over the one registered for the plain subdomain.
+    pub fn alpn_header(mut self, name: HeaderName) -> Self {
+        self.config.alpn_header = Some(name);
+        self
+    }
+
+    /// Require HTTPS for a subdomain.
+    ///
+    /// Plain HTTP requests to the subdomain (per `X-Forwarded-Proto`, or the request URI) receive a
+    /// `308 Permanent Redirect` to the `https://` equivalent with the same host, path and query.
+    /// Requests naming no scheme are let through, as servers terminating TLS in-process leave it
+    /// out; behind a TLS-terminating proxy, have it set `X-Forwarded-Proto`.
+    pub fn force_https<S: Into<String>>(mut self, subdomain: S) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config.force_https.insert(subdomain);
+        self
+    }
+
+    /// Restrict a subdomain to the given request methods.
+    ///
+    /// Other methods are rejected with `405 Method Not Allowed` and an `Allow` header listing
+    /// `methods`, before the subdomain's router is called.
+    pub fn allowed_methods<S: Into<String>>(mut self, subdomain: S, methods: &[Method]) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config
+            .allowed_methods
+            .insert(subdomain, methods.to_vec());
+        self
+    }
+
+    /// Set `Content-Type: value` on a subdomain's responses that have no content type.
+    ///
+    /// Useful for JSON-only API subdomains; content types set by handlers are kept.
+    pub fn default_content_type<S: Into<String>>(
+        mut self,
+        subdomain: S,
+        value: HeaderValue,
+    ) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config.default_content_types.insert(subdomain, value);
+        self
+    }
+
+    /// Limit the headers of requests to a subdomain to `max_count` fields and `max_bytes` of
+    /// names and values combined.
+    ///
+    /// Requests over either limit are rejected with `431 Request Header Fields Too Large`
+    /// before the subdomain's router is called. These limits apply on top of the server's own.
+    pub fn limit_request_headers<S: Into<String>>(
+        mut self,
+        subdomain: S,
+        max_count: usize,
+        max_bytes: usize,
+    ) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config
+            .header_limits
+            .insert(subdomain, (max_count, max_bytes));
+        self
+    }
+
+    /// Remove the given headers from requests to a subdomain before its router is called.
+    ///
+    /// Useful for keeping internal headers (e.g. `X-Internal-Token`) away from public subdomains.
+    /// Like the other per-subdomain policies, `subdomain` may also be a wildcard key such as `*`
+    /// or a [`register_regex`](Self::register_regex) pattern, covering every subdomain it routes.
+    pub fn strip_request_headers<S: Into<String>>(
+        mut self,
+        subdomain: S,
+        names: &[HeaderName],
+    ) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config
+            .stripped_headers
+            .entry(subdomain)
+            .or_default()
+            .extend_from_slice(names);
+        self
+    }
+
+    /// Reject cross-origin state-changing requests to a subdomain with `403 Forbidden`.
+    ///
+    /// Requests with methods other than `GET`, `HEAD`, `OPTIONS` and `TRACE` must carry an
+    /// `Origin` (or, failing that, `Referer`) whose host and port equal the request's `Host`.
+    /// Requests with neither header are let through, as browsers send `Origin` on cross-origin
+    /// requests.
+    pub fn enforce_same_origin<S: Into<String>>(mut self, subdomain: S) -> Self {
+        let subdomain = self.key(subdomain);
+        self.config.same_origin.insert(subdomain);
+        self
+    }
+
+    /// Enable or disable `X-Request-Id` injection for matched subdomain requests.
+    ///
+    /// Requests without the header get a freshly generated UUID. The request ID is echoed on the
+    /// response either way.
+    #[cfg(feature = "request-id")]
+    pub fn inject_request_id(mut self, enable: bool) -> Self {
+        self.config.inject_request_id = enable;
         self
     }
+
+    /// Wrap any compatible `inner` service, boxing it so the resulting service has a fixed type.
+    ///
+    /// Useful when the fallback is built elsewhere, e.g. from a `tower::service_fn`, rather than
+    /// being an axum `Router`. The result can be mounted with `Router::fallback_service` or served
+    /// directly.
+    pub fn boxed_service<S>(&self, inner: S) -> SubdomainService<BoxedInner>
+    where
+        S: Service<Request, Response = Response, Error = Infallible>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Future: Send + 'static,
+    {
+        self.layer(BoxedInner::new(inner))
+    }
 }
 
 impl Default for SubdomainLayer {
@@ -85,28 +2955,107 @@ impl Default for SubdomainLayer {
     }
 }
 
+impl TryFrom<HashMap<String, Router>> for SubdomainLayer {
+    type Error = SubdomainError;
+
+    /// Build a layer from a map of subdomain keys to routers.
+    ///
+    /// Keys are normalized before registration; an empty key is rejected.
+    fn try_from(routes: HashMap<String, Router>) -> Result<Self, Self::Error> {
+        routes
+            .into_iter()
+            .try_fold(Self::new(), |layer, (subdomain, router)| {
+                let subdomain = normalize_key(&subdomain);
+                if subdomain.is_empty() {
+                    return Err(SubdomainError::EmptySubdomain);
+                }
+                Ok(layer.register(subdomain, router))
+            })
+    }
+}
+
+/// Build a handler that responds with a JSON summary of `layer`'s routing configuration.
+///
+/// The summary lists the registered subdomain keys, their count and the main matching options.
+/// It is a snapshot taken when the handler is built, meant to be mounted on an internal admin
+/// router, e.g. `admin.route("/debug/routing", get(routing_debug_handler(&layer)))`.
+pub fn routing_debug_handler<S>(layer: &SubdomainLayer) -> impl Handler<(), S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let config = &layer.config;
+    let mut subdomains: Vec<&str> = layer.config.routes.keys().map(String::as_str).collect();
+    subdomains.sort_unstable();
+
+    let summary = serde_json::json!({
+        "subdomains": subdomains,
+        "count": subdomains.len(),
+        "options": {
+            "strict": config.strict,
+            "strict_paths": config.strict_paths,
+            "known_hosts": config.known_hosts,
+            "auto_detect_domain": config.auto_detect_domain,
+            "require_known_apex": config.require_known_apex,
+            "environment_labels": config.environment_labels,
+            "max_subdomain_depth": config.max_subdomain_depth,
+            "normalize_keys": config.normalize_keys,
+            "apex_host": config.apex_host,
+            "apex_router": config.apex.is_some(),
+            "catch_all": config.catch_all.is_some(),
+            "path_tenant_fallback": config.path_tenant_fallback,
+        },
+    });
+
+    RoutingDebugHandler {
+        body: Arc::from(summary.to_string()),
+    }
+}
+
+/// The handler built by [`routing_debug_handler`].
+#[derive(Clone)]
+struct RoutingDebugHandler {
+    body: Arc<str>,
+}
+
+impl<S> Handler<(), S> for RoutingDebugHandler
+where
+    S: Clone + Send + Sync + 'static,
+{
+    type Future = std::future::Ready<Response>;
+
+    fn call(self, _req: Request, _state: S) -> Self::Future {
+        let response = (
+            [(header::CONTENT_TYPE, "application/json")],
+            self.body.to_string(),
+        )
+            .into_response();
+        std::future::ready(response)
+    }
+}
+
 impl<S> Layer<S> for SubdomainLayer {
     type Service = SubdomainService<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
         SubdomainService {
             inner,
-            routes: self.routes.clone(),
-            strict: self.strict,
-            auto_detect_domain: self.auto_detect_domain,
-            known_hosts: self.known_hosts.clone(),
+            config: Arc::new(self.config.clone()),
         }
     }
 }
 
 /// Service that handles subdomain routing.
+///
+/// The routing table is an immutable snapshot taken when the layer is applied, and each request
+/// looks up its router exactly once, so registrations cannot change between matching a subdomain
+/// and dispatching to it.
+///
+/// Dispatch runs inside the returned future rather than a spawned task, so dropping that future,
+/// as hyper does when a client disconnects, cancels the matched router's handler as well.
 #[derive(Clone)]
 pub struct SubdomainService<S> {
     inner: S,
-    routes: Arc<HashMap<String, Router>>,
-    strict: bool,
-    auto_detect_domain: bool,
-    known_hosts: Arc<Vec<String>>,
+    config: Arc<Config>,
 }
 
 impl<S> Service<Request> for SubdomainService<S>
@@ -124,62 +3073,72 @@ where
 
     fn call(&mut self, req: Request) -> Self::Future {
         let inner = self.inner.clone();
-        let routes = self.routes.clone();
-        let strict = self.strict;
-        let auto_detect_domain = self.auto_detect_domain;
-        let known_hosts = self.known_hosts.clone();
+        let config = self.config.clone();
 
-        // Extract host header before moving req
-        let host = req
-            .headers()
-            .get("host")
-            .and_then(|h| h.to_str().ok())
-            .map(|h| h.split(':').next().unwrap_or(h).to_string());
-
-        Box::pin(async move {
-            if let Some(host) = host {
-                let mut target_subdomain = None;
-
-                // Try known hosts
-                for known in known_hosts.iter() {
-                    if host.ends_with(known) {
-                        let remainder_len = host.len() - known.len();
-                        if remainder_len > 0 && host.as_bytes()[remainder_len - 1] == b'.' {
-                            target_subdomain = Some(host[..remainder_len - 1].to_string());
-                            break;
-                        }
-                    }
-                }
+        #[cfg(feature = "otel")]
+        let span = dispatch_span(&req);
 
-                if target_subdomain.is_none() && auto_detect_domain {
-                    let host = IP_REGEX.replace_all(&host, "$1_$2_$3_$4");
-                    let parts: Vec<&str> = host.split('.').collect();
-                    if !parts.is_empty() {
-                        let last = *parts.last().unwrap();
-                        let mut parts = parts;
-                        if KNOWN_TLDS.contains(&last) {
-                            parts.pop();
-                        }
-                        if parts.len() > 1 {
-                            target_subdomain = Some(parts[..parts.len() - 1].to_vec().join("."));
-                        }
-                    }
-                }
+        let future = async move {
+            let Some(access_log) = &config.access_log else {
+                return config.dispatch(req, inner).await;
+            };
 
-                if let Some(sub) = target_subdomain {
-                    if let Some(router) = routes.get(&sub) {
-                        return router.clone().oneshot(req).await;
-                    } else if strict {
-                        let response = Response::builder()
-                            .status(StatusCode::NOT_FOUND)
-                            .body(axum::body::Body::empty())
-                            .unwrap();
-                        return Ok(response);
-                    }
-                }
-            }
-            // Fallback to inner service
-            inner.oneshot(req).await
-        })
+            let started = Instant::now();
+            let subdomain = config.request_subdomain(&req);
+            let method = req.method().clone();
+            let path = req.uri().path().to_string();
+
+            let response = config.dispatch(req, inner).await?;
+
+            // Never hold up the response for a slow consumer
+            let _ = access_log.try_send(AccessRecord {
+                subdomain,
+                method,
+                path,
+                status: response.status(),
+                duration: started.elapsed(),
+            });
+            Ok(response)
+        };
+
+        #[cfg(feature = "otel")]
+        let future = tracing::Instrument::instrument(future, span);
+
+        Box::pin(future)
+    }
+}
+
+/// Helpers for driving an app through the layer in tests, without a network listener.
+#[cfg(feature = "test-util")]
+pub mod test_util {
+    use axum::{Router, body::Body, extract::Request, http::header, response::Response};
+    use tower::util::ServiceExt;
+
+    /// Send a `GET` request for `path` with the given `Host` header to `app`.
+    pub async fn get(app: &Router, host: &str, path: &str) -> Response {
+        let req = Request::builder()
+            .uri(path)
+            .header(header::HOST, host)
+            .body(Body::empty())
+            .unwrap();
+        send(app, req).await
+    }
+
+    /// Send `req` to `app`, leaving `app` usable for further requests.
+    pub async fn send(app: &Router, req: Request) -> Response {
+        let Ok(response) = app.clone().oneshot(req).await;
+        response
+    }
+
+    /// Read the body of `response` as a string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the body cannot be read or is not valid UTF-8.
+    pub async fn text(response: Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
     }
 }
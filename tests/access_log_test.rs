@@ -0,0 +1,78 @@
+mod common;
+
+use axum::{
+    Router,
+    http::{Method, StatusCode},
+    routing::{get, post},
+};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_access_log_channel() {
+    let api_router = Router::new().route("/users", post(|| async { "created" }));
+
+    let (layer, mut records) = SubdomainLayer::new()
+        .register("api", api_router)
+        .access_log_channel(16);
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer);
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    client
+        .post(format!("http://{}/users", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    client
+        .get(format!("http://{}/missing", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+
+    let record = records.recv().await.unwrap();
+    assert_eq!(record.subdomain.as_deref(), Some("api"));
+    assert_eq!(record.method, Method::POST);
+    assert_eq!(record.path, "/users");
+    assert_eq!(record.status, StatusCode::OK);
+
+    let record = records.recv().await.unwrap();
+    assert_eq!(record.subdomain, None);
+    assert_eq!(record.method, Method::GET);
+    assert_eq!(record.path, "/missing");
+    assert_eq!(record.status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_access_log_drops_when_full() {
+    let (layer, mut records) = SubdomainLayer::new().access_log_channel(1);
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer);
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    for _ in 0..3 {
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", "example.com")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    assert!(records.recv().await.is_some());
+    assert!(records.try_recv().is_err());
+}
+
+#[test]
+#[should_panic(expected = "capacity must be at least 1")]
+fn test_access_log_channel_rejects_zero_capacity() {
+    let _ = SubdomainLayer::new().access_log_channel(0);
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "ahash")]
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_routing_with_ahash() {
+    let layer = (0..200).fold(SubdomainLayer::new().strict(true), |layer, i| {
+        let body = format!("tenant {i}");
+        layer.register(
+            format!("tenant{i}"),
+            Router::new().route("/", get(move || async move { body })),
+        )
+    });
+    assert_eq!(layer.len(), 200);
+    let app = Router::new().layer(layer);
+
+    // Test every registration is found through the alternate hasher
+    for i in [0, 1, 99, 199] {
+        let resp = test_util::get(&app, &format!("tenant{i}.example.com"), "/").await;
+        assert_eq!(test_util::text(resp).await, format!("tenant {i}"));
+    }
+
+    let resp = test_util::get(&app, "tenant200.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
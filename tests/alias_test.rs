@@ -0,0 +1,54 @@
+use axum::{Router, extract::State, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+#[tokio::test]
+async fn test_alias_shares_router() {
+    let builds = Arc::new(AtomicUsize::new(0));
+    let hits = Arc::new(AtomicUsize::new(0));
+
+    let layer = SubdomainLayer::new()
+        .register_with("www", {
+            let (builds, hits) = (builds.clone(), hits.clone());
+            move || {
+                builds.fetch_add(1, Ordering::SeqCst);
+                Router::new()
+                    .route(
+                        "/",
+                        get(|State(hits): State<Arc<AtomicUsize>>| async move {
+                            hits.fetch_add(1, Ordering::SeqCst).to_string()
+                        }),
+                    )
+                    .with_state(hits.clone())
+            }
+        })
+        .alias("www", "app")
+        .alias("www", " Web ");
+    assert_eq!(layer.len(), 3);
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer);
+
+    // Test every alias reaches the same router, which is built once
+    for (host, expected) in [
+        ("www.example.com", "0"),
+        ("app.example.com", "1"),
+        ("web.example.com", "2"),
+        ("www.example.com", "3"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+    assert_eq!(hits.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+#[should_panic(expected = "cannot alias unregistered subdomain `www`")]
+fn test_alias_unregistered_panics() {
+    let _ = SubdomainLayer::new().alias("www", "app");
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use axum::{Router, http::HeaderName, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_alpn_variants_route_differently() {
+    let h2_router = Router::new().route("/", get(|| async { "Hello from h2 API!" }));
+    let h1_router = Router::new().route("/", get(|| async { "Hello from HTTP/1.1 API!" }));
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .alpn_header(HeaderName::from_static("x-forwarded-alpn"))
+                .register("h2:api", h2_router)
+                .register("http/1.1:api", h1_router)
+                .register("api", api_router),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test h2 variant
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .header("X-Forwarded-Alpn", "h2")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from h2 API!");
+
+    // Test http/1.1 variant
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .header("X-Forwarded-Alpn", "http/1.1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from HTTP/1.1 API!");
+
+    // Test unknown protocol falls back to the plain registration
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .header("X-Forwarded-Alpn", "h3")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    // Test missing header uses the plain registration
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_redirect_apex_to_subdomain() {
+    let app_router = Router::new().route("/", get(|| async { "Hello from App!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("app", app_router)
+                .redirect_apex_to("app", StatusCode::MOVED_PERMANENTLY),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    // Test the apex redirects, preserving path and query
+    let resp = client
+        .get(format!("http://{}/pricing?plan=pro", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 301);
+    assert_eq!(
+        resp.headers().get("location").unwrap(),
+        "http://app.example.com/pricing?plan=pro"
+    );
+
+    // Test the target subdomain itself is served
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from App!");
+}
+
+#[tokio::test]
+async fn test_redirect_apex_to_self_does_not_loop() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().redirect_apex_to("example.com", StatusCode::FOUND));
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
+
+#[tokio::test]
+async fn test_redirect_apex_keeps_port() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("app", Router::new())
+            .redirect_apex_to("app", StatusCode::FOUND),
+    );
+
+    for (host, expected) in [
+        ("example.com:8080", "http://app.example.com:8080/a?b"),
+        ("localhost:3000", "http://app.localhost:3000/a?b"),
+        ("example.com", "http://app.example.com/a?b"),
+    ] {
+        let resp = test_util::get(&app, host, "/a?b").await;
+        assert_eq!(resp.status(), StatusCode::FOUND, "{host}");
+        assert_eq!(resp.headers()["location"], expected, "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_redirect_apex_uses_registrable_domain() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("app", Router::new())
+            .ignore_prefixes(vec!["www".to_string()])
+            .redirect_apex_to("app", StatusCode::FOUND),
+    );
+
+    // Test an ignored prefix is dropped rather than nested under the target
+    for (host, expected) in [
+        ("www.example.com", "http://app.example.com/"),
+        ("www.example.co.uk", "http://app.example.co.uk/"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(resp.status(), StatusCode::FOUND, "{host}");
+        assert_eq!(resp.headers()["location"], expected, "{host}");
+    }
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+#[tokio::test]
+async fn test_register_apex_and_subdomain() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let app_router =
+        Router::new().route(
+            "/",
+            get({
+                let hits = hits.clone();
+                move || async move {
+                    format!("Hello from App! #{}", hits.fetch_add(1, Ordering::SeqCst))
+                }
+            }),
+        );
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register_apex_and_subdomain("app", app_router)
+                .register("api", api_router),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the apex and the subdomain hit the same handler
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from App! #0");
+
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from App! #1");
+
+    // Test other subdomains are unaffected
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
+
+#[tokio::test]
+async fn test_register_apex_for_with_port() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register_apex_for(
+            "Example.NET:8080",
+            Router::new().route("/", get(|| async { "Welcome to example.net" })),
+        ));
+
+    // Test a configured port is ignored like in request hosts
+    for host in ["example.net:8080", "example.net"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(
+            test_util::text(resp).await,
+            "Welcome to example.net",
+            "{host}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_register_apex_for() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .known_hosts(vec![
+                    "example.com".to_string(),
+                    "example.net".to_string(),
+                    "example.org".to_string(),
+                ])
+                .register_apex_for(
+                    "example.com",
+                    Router::new().route("/", get(|| async { "Welcome to example.com" })),
+                )
+                .register_apex_for(
+                    "example.net",
+                    Router::new().route("/", get(|| async { "Welcome to example.net" })),
+                ),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test each base domain serves its own apex
+    for (host, expected) in [
+        ("example.com", "Welcome to example.com"),
+        ("example.net:8080", "Welcome to example.net"),
+        ("example.org", "Hello from Main App!"),
+    ] {
+        let resp = client
+            .get(format!("http://{}/", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), expected, "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_apex_router() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", Router::new().route("/", get(|| async { "api" })))
+                .apex_router(Router::new().route("/", get(|| async { "apex" })))
+                .known_hosts(vec!["example.internal".to_string()]),
+        );
+
+    for (host, expected) in [
+        // Test hosts without a subdomain reach the apex router
+        ("example.com", "apex"),
+        ("example.internal", "apex"),
+        // Test matched and unmatched subdomains are unaffected
+        ("api.example.com", "api"),
+        ("www.example.com", "Hello from Main App!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
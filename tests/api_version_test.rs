@@ -0,0 +1,36 @@
+use axum::{Router, extract::Request, routing::get};
+use axum_subdomain_routing::{ApiVersion, SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_version_label_prefix() {
+    let api_router = Router::new().route(
+        "/",
+        get(|req: Request| async move {
+            match req.extensions().get::<ApiVersion>() {
+                Some(ApiVersion(version)) => format!("api {version}"),
+                None => "api unversioned".to_string(),
+            }
+        }),
+    );
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .register("v1", Router::new().route("/", get(|| async { "v1 site" })))
+                .version_label_prefix(true),
+        );
+
+    // Test the version label is stripped and exposed
+    for (host, expected) in [
+        ("v2.api.example.com", "api v2"),
+        ("v10.api.example.com", "api v10"),
+        ("api.example.com", "api unversioned"),
+        ("v1.example.com", "v1 site"),
+        ("version.api.example.com", "Hello from Main App!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
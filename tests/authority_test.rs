@@ -0,0 +1,34 @@
+use axum::{Router, body::Body, http::Request, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app() -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register(
+            "api",
+            Router::new().route("/", get(|| async { "Hello from API!" })),
+        ))
+}
+
+#[tokio::test]
+async fn test_uri_authority_without_host_header() {
+    // Test an HTTP/2-style request carrying the host only in its URI authority
+    let req = Request::builder()
+        .uri("https://api.example.com:8443/")
+        .body(Body::empty())
+        .unwrap();
+    assert!(req.headers().get("host").is_none());
+    let resp = test_util::send(&app(), req).await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+}
+
+#[tokio::test]
+async fn test_host_header_takes_precedence_over_authority() {
+    let req = Request::builder()
+        .uri("https://api.example.com/")
+        .header("host", "www.example.com")
+        .body(Body::empty())
+        .unwrap();
+    let resp = test_util::send(&app(), req).await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+}
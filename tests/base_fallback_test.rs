@@ -0,0 +1,44 @@
+use axum::{Router, http::StatusCode, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(strict: bool) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", Router::new().route("/", get(|| async { "api" })))
+                .fallback_for(
+                    "a.com",
+                    Router::new().route("/", get(|| async { "a.com fallback" })),
+                )
+                .fallback_for(
+                    "b.com",
+                    Router::new().route("/", get(|| async { "b.com fallback" })),
+                )
+                .strict(strict),
+        )
+}
+
+#[tokio::test]
+async fn test_fallback_for_base_domains() {
+    let app = app(false);
+
+    for (host, expected) in [
+        // Test unknown subdomains hit their base domain's fallback
+        ("unknown.a.com", "a.com fallback"),
+        ("unknown.b.com", "b.com fallback"),
+        // Test known subdomains and other bases are unaffected
+        ("api.a.com", "api"),
+        ("unknown.c.com", "Hello from Main App!"),
+        ("a.com", "Hello from Main App!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_fallback_for_respects_strict() {
+    let resp = test_util::get(&app(true), "unknown.a.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use std::convert::Infallible;
+
+use axum::{
+    Router,
+    extract::Request,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_boxed_inner_service() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let fallback = tower::service_fn(|req: Request| async move {
+        Ok::<Response, Infallible>(format!("fallback for {}", req.uri().path()).into_response())
+    });
+
+    let service = SubdomainLayer::new()
+        .register("api", api_router)
+        .boxed_service(fallback);
+    let app = Router::new().fallback_service(service);
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test matched subdomains are routed
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    // Test unmatched hosts reach the boxed inner service
+    for host in ["unknown.example.com", "example.com"] {
+        let resp = client
+            .get(format!("http://{}/page", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "fallback for /page");
+    }
+}
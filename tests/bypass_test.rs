@@ -0,0 +1,57 @@
+mod common;
+
+use axum::{Router, http::HeaderName, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_bypass_header() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .strict(true)
+                .bypass_header(HeaderName::from_static("x-bypass-subdomain")),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test truthy values bypass matching
+    for value in ["1", "true", "YES"] {
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", "api.example.com")
+            .header("X-Bypass-Subdomain", value)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.text().await.unwrap(),
+            "Hello from Main App!",
+            "{value}"
+        );
+    }
+
+    // Test bypass also skips strict mode
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "unknown.example.com")
+        .header("X-Bypass-Subdomain", "1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Test falsy values route normally
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .header("X-Bypass-Subdomain", "0")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
@@ -0,0 +1,50 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+/// Sets its flag when dropped.
+struct DropGuard(Arc<AtomicBool>);
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn test_dropping_response_future_cancels_handler() {
+    let started = Arc::new(AtomicBool::new(false));
+    let dropped = Arc::new(AtomicBool::new(false));
+
+    let api_router = Router::new().route(
+        "/slow",
+        get({
+            let (started, dropped) = (started.clone(), dropped.clone());
+            move || async move {
+                let _guard = DropGuard(dropped);
+                started.store(true, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+                "done"
+            }
+        }),
+    );
+
+    let app = Router::new().layer(SubdomainLayer::new().register("api", api_router));
+
+    // Test the in-flight handler is dropped with the response future
+    let result = tokio::time::timeout(
+        Duration::from_millis(50),
+        test_util::get(&app, "api.example.com", "/slow"),
+    )
+    .await;
+    assert!(result.is_err());
+    assert!(started.load(Ordering::SeqCst));
+    assert!(dropped.load(Ordering::SeqCst));
+}
@@ -0,0 +1,69 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{MatchedSubdomain, SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_case_insensitive_matching() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", Router::new().route("/", get(|| async { "api" })))
+                .register("Admin", Router::new().route("/", get(|| async { "admin" })))
+                .known_hosts(vec!["Example.Com".to_string()])
+                .strict(true),
+        );
+
+    for (host, expected) in [
+        // Test mixed-case hosts match lowercase registrations
+        ("Api.Example.COM", "api"),
+        ("API.example.com:8080", "api"),
+        // Test mixed-case registrations match any host casing
+        ("admin.example.com", "admin"),
+        ("ADMIN.EXAMPLE.COM", "admin"),
+        // Test the apex suffix is matched case-insensitively
+        ("EXAMPLE.com", "Hello from Main App!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_matched_subdomain_preserves_case() {
+    let app = Router::new().layer(SubdomainLayer::new().register(
+        "api",
+        Router::new().route(
+            "/",
+            get(|MatchedSubdomain(subdomain): MatchedSubdomain| async move { subdomain }),
+        ),
+    ));
+
+    // Test the handler sees the subdomain as the client cased it
+    let resp = test_util::get(&app, "API.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "API");
+
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "api");
+}
+
+#[tokio::test]
+async fn test_matched_subdomain_preserves_case_after_stripping() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register(
+                "api",
+                Router::new().route(
+                    "/",
+                    get(|MatchedSubdomain(subdomain): MatchedSubdomain| async move { subdomain }),
+                ),
+            )
+            .ignore_prefixes(vec!["www".to_string()])
+            .version_label_prefix(true),
+    );
+
+    // Test the key is mapped back to its labels when leading labels were stripped
+    for host in ["V2.API.example.com", "www.API.example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, "API", "{host}");
+    }
+}
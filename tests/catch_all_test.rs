@@ -0,0 +1,42 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_catch_all_receives_every_host() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+    let catch_all = Router::new().fallback(|| async { "Hello from Catch-All!" });
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .strict(true)
+                .catch_all(catch_all),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    for host in [
+        "example.com",
+        "api.example.com",
+        "unknown.example.com",
+        "127.0.0.1",
+    ] {
+        let resp = client
+            .get(format!("http://{}/any/path", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200, "{host}");
+        assert_eq!(
+            resp.text().await.unwrap(),
+            "Hello from Catch-All!",
+            "{host}"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{HeaderValue, header},
+    response::Response,
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+/// A response without any content type.
+async fn untyped() -> Response {
+    Response::new(Body::from(r#"{"ok":true}"#))
+}
+
+#[tokio::test]
+async fn test_default_content_type() {
+    let app = Router::new().route("/", get(untyped)).layer(
+        SubdomainLayer::new()
+            .register(
+                "api",
+                Router::new()
+                    .route("/", get(untyped))
+                    .route("/text", get(|| async { "plain" })),
+            )
+            .register("www", Router::new().route("/", get(untyped)))
+            .default_content_type("api", HeaderValue::from_static("application/json")),
+    );
+
+    // Test the default is applied to untyped responses of the subdomain
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+
+    // Test handler-set content types are kept
+    let resp = test_util::get(&app, "api.example.com", "/text").await;
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+
+    // Test other subdomains and the apex are unaffected
+    for host in ["www.example.com", "example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert!(resp.headers().get(header::CONTENT_TYPE).is_none(), "{host}");
+    }
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, routing_debug_handler};
+
+#[tokio::test]
+async fn test_routing_debug_handler() {
+    let layer = SubdomainLayer::new()
+        .register("api", Router::new())
+        .register("admin", Router::new())
+        .strict(true);
+
+    let app = Router::new()
+        .route("/debug/routing", get(routing_debug_handler(&layer)))
+        .layer(layer);
+
+    let addr = common::serve(app).await;
+
+    let resp = reqwest::Client::new()
+        .get(format!("http://{}/debug/routing", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["content-type"], "application/json");
+
+    // Test the registered keys and options are reported
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["subdomains"], serde_json::json!(["admin", "api"]));
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["options"]["strict"], true);
+}
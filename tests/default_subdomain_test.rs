@@ -0,0 +1,47 @@
+use axum::{Extension, Router, http::StatusCode, response::IntoResponse, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, UnmatchedSubdomain, test_util};
+
+fn layer() -> SubdomainLayer {
+    SubdomainLayer::new()
+        .register(
+            "api",
+            Router::new().route("/", get(|| async { "Hello from API!" })),
+        )
+        .default_subdomain_router(Router::new().route(
+            "/",
+            get(
+                |Extension(UnmatchedSubdomain(tenant)): Extension<UnmatchedSubdomain>| async move {
+                    (StatusCode::NOT_FOUND, format!("No tenant named {tenant}")).into_response()
+                },
+            ),
+        ))
+}
+
+#[tokio::test]
+async fn test_default_subdomain_router() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer());
+
+    // Test apex requests reach the main router
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+
+    // Test matched subdomains reach their router
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+
+    // Test unmatched subdomains reach the default router with the attempted subdomain
+    let resp = test_util::get(&app, "Acme.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(test_util::text(resp).await, "No tenant named Acme");
+}
+
+#[tokio::test]
+async fn test_strict_takes_precedence_over_default_subdomain_router() {
+    let app = Router::new().layer(layer().strict(true));
+
+    let resp = test_util::get(&app, "acme.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(test_util::text(resp).await, "");
+}
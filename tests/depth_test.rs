@@ -0,0 +1,50 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_max_subdomain_depth() {
+    let a_router = Router::new().route("/", get(|| async { "Hello from A!" }));
+    let nested_router = Router::new().route("/", get(|| async { "Hello from A.B!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("a", a_router)
+                .register("a.b", nested_router)
+                .max_subdomain_depth(1),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test direct subdomain is allowed
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "a.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from A!");
+
+    // Test nested subdomain is rejected even though it is registered
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "a.b.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+
+    // Test apex is unaffected
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
@@ -0,0 +1,42 @@
+use axum::{Router, http::StatusCode, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn layer() -> SubdomainLayer {
+    SubdomainLayer::new()
+        .register(
+            "api",
+            Router::new().route("/", get(|| async { "Hello from API!" })),
+        )
+        .register(
+            "admin",
+            Router::new().route("/", get(|| async { "Hello from Admin!" })),
+        )
+        .strict(true)
+}
+
+#[tokio::test]
+async fn test_dev_mode_lists_subdomains() {
+    let app = Router::new().layer(layer().dev_mode(true));
+
+    let resp = test_util::get(&app, "admn.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let body = test_util::text(resp).await;
+    assert!(body.contains("`admn`"));
+    assert!(body.contains("Did you mean `admin`?"));
+    assert!(body.contains("Registered subdomains: admin, api"));
+
+    // Test no suggestion is made for an unrelated subdomain
+    let resp = test_util::get(&app, "zzzzzzzz.example.com", "/").await;
+    let body = test_util::text(resp).await;
+    assert!(!body.contains("Did you mean"));
+    assert!(body.contains("Registered subdomains: admin, api"));
+}
+
+#[tokio::test]
+async fn test_dev_mode_off_by_default() {
+    let app = Router::new().layer(layer());
+
+    let resp = test_util::get(&app, "admn.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(test_util::text(resp).await, "");
+}
@@ -0,0 +1,101 @@
+use axum::{
+    Router,
+    http::{HeaderMap, HeaderValue},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+async fn tenant_header(headers: HeaderMap) -> String {
+    headers
+        .get("x-tenant")
+        .map_or("none", |value| value.to_str().unwrap())
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_before_dispatch() {
+    let app = Router::new().route("/", get(tenant_header)).layer(
+        SubdomainLayer::new()
+            .register("api", Router::new().route("/", get(tenant_header)))
+            .before_dispatch(|req, subdomain| {
+                let tenant = HeaderValue::from_str(subdomain.unwrap_or("main")).unwrap();
+                req.headers_mut().insert("x-tenant", tenant);
+            }),
+    );
+
+    // Test the hook sees the matched subdomain
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "api");
+
+    // Test the hook also runs for the fallback path
+    let resp = test_util::get(&app, "www.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "main");
+}
+
+#[tokio::test]
+async fn test_after_dispatch() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .after_dispatch(|response, subdomain| {
+                    let served_by = HeaderValue::from_str(subdomain.unwrap_or("main")).unwrap();
+                    response.headers_mut().insert("x-served-by", served_by);
+                }),
+        );
+
+    // Test the hook sees matched responses with their subdomain
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(resp.headers()["x-served-by"], "api");
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+
+    // Test the hook also sees fallback responses
+    let resp = test_util::get(&app, "www.example.com", "/").await;
+    assert_eq!(resp.headers()["x-served-by"], "main");
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+}
+
+/// An app whose apex, full-host and base-domain fallback routers all answer with `handler`.
+fn other_routers_app(layer: SubdomainLayer) -> Router {
+    let router = || Router::new().route("/", get(tenant_header));
+    Router::new().layer(
+        layer
+            .register("api", router())
+            .apex_router(router())
+            .register_host("admin.other.org", router())
+            .fallback_for("b.com", router()),
+    )
+}
+
+#[tokio::test]
+async fn test_before_dispatch_runs_for_other_routers() {
+    let app = other_routers_app(SubdomainLayer::new().before_dispatch(|req, subdomain| {
+        let tenant = HeaderValue::from_str(subdomain.unwrap_or("main")).unwrap();
+        req.headers_mut().insert("x-tenant", tenant);
+    }));
+
+    // Test the apex, host and base-domain fallback routers see the hook's header
+    for host in ["example.com", "admin.other.org", "x.b.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, "main", "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_after_dispatch_runs_for_other_routers() {
+    let app = other_routers_app(SubdomainLayer::new().after_dispatch(|response, _| {
+        response
+            .headers_mut()
+            .insert("x-after", HeaderValue::from_static("yes"));
+    }));
+
+    // Test the apex, host and base-domain fallback responses pass through the hook
+    for host in ["example.com", "admin.other.org", "x.b.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(resp.headers()["x-after"], "yes", "{host}");
+    }
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_environment_labels_are_stripped() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .environment_labels(vec!["staging".to_string(), "dev".to_string()])
+                .register("api", api_router)
+                .strict(true),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    for host in [
+        "api.example.com",
+        "api.staging.example.com",
+        "api.dev.example.com",
+    ] {
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200, "{host}");
+        assert_eq!(resp.text().await.unwrap(), "Hello from API!", "{host}");
+    }
+
+    // Test an environment-only subdomain is treated as the apex
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "staging.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+
+    // Test unknown subdomains in an environment still 404 in strict mode
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "unknown.staging.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
+
+#[tokio::test]
+async fn test_environment_labels_are_case_insensitive() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .environment_labels(vec!["Staging".to_string()])
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                ),
+        );
+
+    for host in ["api.staging.example.com", "api.STAGING.example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, "Hello from API!", "{host}");
+    }
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use axum::{Router, http::Method, routing::get};
+use axum_subdomain_routing::{CorsConfig, SubdomainLayer};
+
+#[tokio::test]
+async fn test_error_cors_on_layer_responses() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .strict(true)
+                .allowed_methods("api", &[Method::GET])
+                .error_cors(CorsConfig::new().allow_origin("https://app.example.com")),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the strict 404 carries the allowed origin
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "unknown.example.com")
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+    assert_eq!(
+        resp.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+
+    // Test other layer-generated responses carry it too
+    let resp = client
+        .delete(format!("http://{}/", addr))
+        .header("Host", "api.example.com")
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 405);
+    assert_eq!(
+        resp.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+
+    // Test router responses and disallowed origins are left alone
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.example.com")
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(!resp.headers().contains_key("access-control-allow-origin"));
+
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "unknown.example.com")
+        .header("Origin", "https://evil.example.org")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+    assert!(!resp.headers().contains_key("access-control-allow-origin"));
+}
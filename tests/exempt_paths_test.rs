@@ -0,0 +1,32 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_exempt_paths() {
+    let api_router = Router::new()
+        .route("/", get(|| async { "Hello from API!" }))
+        .route("/metrics", get(|| async { "api metrics" }));
+
+    let app = Router::new()
+        .route("/metrics", get(|| async { "main metrics" }))
+        .route("/favicon.ico", get(|| async { "main favicon" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .strict(true)
+                .exempt_paths(vec!["/metrics".to_string(), "/favicon.ico".to_string()]),
+        );
+
+    // Test exempt paths reach the main router on every host, even in strict mode
+    for host in ["api.example.com", "unknown.example.com", "example.com"] {
+        let resp = test_util::get(&app, host, "/metrics").await;
+        assert_eq!(test_util::text(resp).await, "main metrics", "{host}");
+
+        let resp = test_util::get(&app, host, "/favicon.ico").await;
+        assert_eq!(test_util::text(resp).await, "main favicon", "{host}");
+    }
+
+    // Test other paths are still routed by subdomain
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use axum::{Extension, Router, routing::get};
+use axum_subdomain_routing::{Extraction, OriginalHost, SubdomainLayer};
+
+#[tokio::test]
+async fn test_extractor_with_effective_host() {
+    let api_router = Router::new().route(
+        "/",
+        get(|Extension(host): Extension<OriginalHost>| async move {
+            format!("{} via {}", host.effective, host.raw)
+        }),
+    );
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .extractor(|host| {
+                    let effective_host = host.strip_prefix("staging.").unwrap_or(host).to_string();
+                    let key = effective_host
+                        .strip_suffix(".example.com")
+                        .map(str::to_string);
+                    Extraction {
+                        key,
+                        effective_host,
+                    }
+                }),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the stripped label routes to the key and exposes the cleaned host
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "staging.api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.text().await.unwrap(),
+        "api.example.com via staging.api.example.com"
+    );
+
+    // Test hosts without the label are unchanged
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.example.com:8080")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.text().await.unwrap(),
+        "api.example.com via api.example.com"
+    );
+
+    // Test a host the extractor yields no key for falls back to the main app
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "staging.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
@@ -0,0 +1,149 @@
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    http::{HeaderMap, HeaderValue, Request, StatusCode, header},
+    routing::{get, post},
+};
+use axum_subdomain_routing::{CorsConfig, SubdomainLayer, test_util};
+use futures::stream;
+use http_body_util::BodyExt;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A body claiming a small exact length that fails when read.
+struct FailingBody;
+
+impl hyper::body::Body for FailingBody {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(Some(Err(std::io::Error::other("connection reset"))))
+    }
+
+    fn size_hint(&self) -> hyper::body::SizeHint {
+        hyper::body::SizeHint::with_exact(4)
+    }
+}
+
+fn app(fallthrough: bool) -> Router {
+    app_with(SubdomainLayer::new().fallthrough_paths(fallthrough))
+}
+
+fn app_with(layer: SubdomainLayer) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .route("/shared", get(|| async { "shared from main" }))
+        .route(
+            "/echo",
+            post(|body: String| async move { format!("main {body}") }),
+        )
+        .layer(layer.register(
+            "api",
+            Router::new().route("/", get(|| async { "Hello from API!" })),
+        ))
+}
+
+fn post_echo(body: Body) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/echo")
+        .header("host", "api.example.com")
+        .body(body)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_fallthrough_paths() {
+    let app = app(true);
+
+    // Test the subdomain's own routes still take precedence
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+
+    // Test paths only the main router has are reachable through the subdomain
+    let resp = test_util::get(&app, "api.example.com", "/shared").await;
+    assert_eq!(test_util::text(resp).await, "shared from main");
+
+    // Test buffered bodies are replayed to the main router
+    let resp = test_util::send(&app, post_echo(Body::from("ping"))).await;
+    assert_eq!(test_util::text(resp).await, "main ping");
+
+    // Test unknown paths still end in a 404
+    let resp = test_util::get(&app, "api.example.com", "/missing").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_fallthrough_skips_streaming_bodies() {
+    let chunks = stream::iter([Ok::<_, std::io::Error>("ping")]);
+    let resp = test_util::send(&app(true), post_echo(Body::from_stream(chunks))).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_fallthrough_disabled_by_default() {
+    let resp = test_util::get(&app(false), "api.example.com", "/shared").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_fallthrough_honors_no_fallback() {
+    let app = app_with(SubdomainLayer::new().fallthrough_paths(true).no_fallback());
+    let resp = test_util::get(&app, "api.example.com", "/shared").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_fallthrough_runs_dispatch_hooks() {
+    let app = Router::new()
+        .route(
+            "/shared",
+            get(|headers: HeaderMap| async move { format!("{:?}", headers["x-stage"]) }),
+        )
+        .layer(
+            SubdomainLayer::new()
+                .register("api", Router::new())
+                .fallthrough_paths(true)
+                .before_dispatch(|req, subdomain| {
+                    let stage = HeaderValue::from_static(if subdomain.is_some() {
+                        "subdomain"
+                    } else {
+                        "main"
+                    });
+                    req.headers_mut().insert("x-stage", stage);
+                })
+                .after_dispatch(|resp, _| {
+                    resp.headers_mut()
+                        .append("x-after", HeaderValue::from_static("yes"));
+                }),
+        );
+
+    // Test the retry is treated as a fallback by both hooks
+    let resp = test_util::get(&app, "api.example.com", "/shared").await;
+    assert_eq!(resp.headers().get_all("x-after").iter().count(), 1);
+    assert_eq!(test_util::text(resp).await, r#""main""#);
+}
+
+#[tokio::test]
+async fn test_fallthrough_body_error_gets_error_cors() {
+    let app = app_with(
+        SubdomainLayer::new()
+            .fallthrough_paths(true)
+            .error_cors(CorsConfig::new()),
+    );
+    let req = Request::builder()
+        .method("POST")
+        .uri("/echo")
+        .header("host", "api.example.com")
+        .body(Body::new(FailingBody.map_err(axum::Error::new)))
+        .unwrap();
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(resp.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+}
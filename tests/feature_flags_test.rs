@@ -0,0 +1,48 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::{Extension, Router, routing::get};
+use axum_subdomain_routing::{FeatureFlags, SubdomainLayer};
+
+async fn checkout(Extension(flags): Extension<FeatureFlags>) -> &'static str {
+    if flags.is_enabled("new-checkout") {
+        "new checkout"
+    } else {
+        "old checkout"
+    }
+}
+
+#[tokio::test]
+async fn test_register_with_flags() {
+    let tenant = || Router::new().route("/checkout", get(checkout));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register_with_flags(
+                    "acme",
+                    tenant(),
+                    HashSet::from(["new-checkout".to_string(), "beta".to_string()]),
+                )
+                .register_with_flags("globex", tenant(), HashSet::new()),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the same handler sees each tenant's flags
+    for (host, expected) in [
+        ("acme.example.com", "new checkout"),
+        ("globex.example.com", "old checkout"),
+    ] {
+        let resp = client
+            .get(format!("http://{}/checkout", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), expected);
+    }
+}
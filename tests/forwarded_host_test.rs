@@ -0,0 +1,50 @@
+use axum::{Router, body::Body, http::Request, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(trust: bool) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .trust_forwarded_host(trust),
+        )
+}
+
+fn forwarded(forwarded_host: &str) -> Request<Body> {
+    Request::builder()
+        .uri("/")
+        .header("host", "upstream.internal")
+        .header("x-forwarded-host", forwarded_host)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_forwarded_host_ignored_by_default() {
+    let resp = test_util::send(&app(false), forwarded("api.example.com")).await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+}
+
+#[tokio::test]
+async fn test_forwarded_host_trusted() {
+    let app = app(true);
+
+    // Test the forwarded host is routed on, without its port
+    let resp = test_util::send(&app, forwarded("api.example.com:8443")).await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+
+    // Test only the first host of a forwarded list is used
+    let resp = test_util::send(&app, forwarded("api.example.com, proxy.example.com")).await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+
+    let resp = test_util::send(&app, forwarded("www.example.com, api.example.com")).await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+
+    // Test the Host header is still used without a forwarded host
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+}
@@ -0,0 +1,41 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn request(host: &str, headers: usize, value: &str) -> Request<Body> {
+    let mut builder = Request::builder().uri("/").header("host", host);
+    for i in 0..headers {
+        builder = builder.header(format!("x-extra-{i}"), value);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_limit_request_headers() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("admin", Router::new().route("/", get(|| async { "admin" })))
+            .register("api", Router::new().route("/", get(|| async { "api" })))
+            .limit_request_headers("admin", 10, 1024),
+    );
+
+    // Test requests within the limits pass
+    let resp = test_util::send(&app, request("admin.example.com", 5, "x")).await;
+    assert_eq!(test_util::text(resp).await, "admin");
+
+    // Test too many headers are rejected
+    let resp = test_util::send(&app, request("admin.example.com", 20, "x")).await;
+    assert_eq!(resp.status(), StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+
+    // Test oversized headers are rejected
+    let resp = test_util::send(&app, request("admin.example.com", 1, &"x".repeat(2048))).await;
+    assert_eq!(resp.status(), StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+
+    // Test other subdomains are unaffected
+    let resp = test_util::send(&app, request("api.example.com", 20, "x")).await;
+    assert_eq!(test_util::text(resp).await, "api");
+}
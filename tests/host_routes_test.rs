@@ -0,0 +1,37 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_register_host() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "admin",
+                    Router::new().route("/", get(|| async { "admin subdomain" })),
+                )
+                .register_host(
+                    "admin.example.com",
+                    Router::new().route("/", get(|| async { "example admin" })),
+                )
+                .register_host(
+                    "Admin.Other.Org:8443",
+                    Router::new().route("/", get(|| async { "other admin" })),
+                ),
+        );
+
+    for (host, expected) in [
+        // Test full hosts sharing a subdomain label map to distinct routers
+        ("admin.example.com", "example admin"),
+        ("admin.other.org", "other admin"),
+        // Test the port and case are ignored
+        ("ADMIN.example.com:3000", "example admin"),
+        // Test unlisted hosts fall through to subdomain routing
+        ("admin.example.net", "admin subdomain"),
+        ("www.example.com", "Hello from Main App!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
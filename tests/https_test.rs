@@ -0,0 +1,188 @@
+mod common;
+
+use axum::{Router, body::Body, http::Request, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_force_https_redirects_plain_http() {
+    let admin_router = Router::new().route("/panel", get(|| async { "Hello from Admin!" }));
+    let api_router = Router::new().route("/panel", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("admin", admin_router)
+                .register("api", api_router)
+                .force_https("admin"),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    // Test plain http is redirected, preserving host, path and query
+    let resp = client
+        .get(format!("http://{}/panel?tab=users", addr))
+        .header("Host", format!("admin.example.com:{}", addr.port()))
+        .header("X-Forwarded-Proto", "http")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 308);
+    assert_eq!(
+        resp.headers().get("location").unwrap(),
+        "https://admin.example.com/panel?tab=users"
+    );
+
+    // Test a request naming no scheme is not redirected to itself
+    let resp = client
+        .get(format!("http://{}/panel", addr))
+        .header("Host", "admin.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Test https forwarded proto routes normally
+    let resp = client
+        .get(format!("http://{}/panel", addr))
+        .header("Host", "admin.example.com")
+        .header("X-Forwarded-Proto", "https")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from Admin!");
+
+    // Test other subdomains are not affected
+    let resp = client
+        .get(format!("http://{}/panel", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
+
+#[tokio::test]
+async fn test_force_https_uses_apex_host() {
+    let admin_router = Router::new().route("/", get(|| async { "Hello from Admin!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .known_hosts(vec!["internal.lan".to_string()])
+                .register("admin", admin_router)
+                .force_https("admin")
+                .apex_host("example.com"),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    // Test the redirect targets the configured apex rather than the internal host
+    let resp = client
+        .get(format!("http://{}/login?next=%2F", addr))
+        .header("Host", "admin.internal.lan")
+        .header("X-Forwarded-Proto", "http")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 308);
+    assert_eq!(
+        resp.headers().get("location").unwrap(),
+        "https://admin.example.com/login?next=%2F"
+    );
+}
+
+#[tokio::test]
+async fn test_force_https_apex_host_across_base_domains() {
+    let app = |layer: SubdomainLayer| {
+        Router::new().layer(
+            layer
+                .known_hosts(vec!["example.com".to_string(), "example.net".to_string()])
+                .register("admin", Router::new())
+                .force_https("admin"),
+        )
+    };
+    let request = || {
+        Request::builder()
+            .uri("/x?y=1")
+            .header("host", "admin.example.net")
+            .header("x-forwarded-proto", "http")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // Test the apex host canonicalizes every base domain onto itself
+    let resp = test_util::send(
+        &app(SubdomainLayer::new().apex_host("example.com")),
+        request(),
+    )
+    .await;
+    assert_eq!(resp.status(), 308);
+    assert_eq!(
+        resp.headers()["location"],
+        "https://admin.example.com/x?y=1"
+    );
+
+    // Test each base domain is kept when no apex host is set
+    let resp = test_util::send(&app(SubdomainLayer::new()), request()).await;
+    assert_eq!(resp.status(), 308);
+    assert_eq!(
+        resp.headers()["location"],
+        "https://admin.example.net/x?y=1"
+    );
+}
+
+#[tokio::test]
+async fn test_force_https_wildcard_key() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("*", Router::new().route("/", get(|| async { "tenant" })))
+            .force_https("*"),
+    );
+
+    let req = Request::builder()
+        .uri("/")
+        .header("host", "tenant1.example.com")
+        .header("x-forwarded-proto", "http")
+        .body(Body::empty())
+        .unwrap();
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(resp.status(), 308);
+    assert_eq!(resp.headers()["location"], "https://tenant1.example.com/");
+}
+
+#[tokio::test]
+async fn test_force_https_uri_scheme() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("admin", Router::new().route("/", get(|| async { "admin" })))
+            .force_https("admin"),
+    );
+
+    // Test an absolute http URI is redirected and an https one routes
+    let req = Request::builder()
+        .uri("http://admin.example.com/")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(test_util::send(&app, req).await.status(), 308);
+
+    let req = Request::builder()
+        .uri("https://admin.example.com/")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        test_util::text(test_util::send(&app, req).await).await,
+        "admin"
+    );
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "idna")]
+
+use axum::{
+    Router,
+    body::Body,
+    http::{HeaderValue, Request},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app() -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "münchen",
+                    Router::new().route("/", get(|| async { "münchen" })),
+                )
+                .register(
+                    "xn--zrich-kva",
+                    Router::new().route("/", get(|| async { "zürich" })),
+                ),
+        )
+}
+
+fn utf8_host(host: &str) -> Request<Body> {
+    Request::builder()
+        .uri("/")
+        .header("host", HeaderValue::from_bytes(host.as_bytes()).unwrap())
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_punycode_host_matches_unicode_registration() {
+    let resp = test_util::get(&app(), "xn--mnchen-3ya.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "münchen");
+
+    let resp = test_util::send(&app(), utf8_host("MÜNCHEN.example.com")).await;
+    assert_eq!(test_util::text(resp).await, "münchen");
+}
+
+#[tokio::test]
+async fn test_unicode_host_matches_punycode_registration() {
+    let app = app();
+    assert!(
+        SubdomainLayer::new()
+            .register("xn--zrich-kva", Router::new())
+            .contains("zürich")
+    );
+
+    let resp = test_util::send(&app, utf8_host("zürich.example.com")).await;
+    assert_eq!(test_util::text(resp).await, "zürich");
+
+    let resp = test_util::get(&app, "xn--zrich-kva.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "zürich");
+}
+
+#[tokio::test]
+async fn test_punycode_configured_hosts() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .known_hosts(vec!["xn--mnchen-3ya.test".to_string()])
+                .without_tlds()
+                .register("api", Router::new().route("/", get(|| async { "api" })))
+                .register_apex_for(
+                    "xn--mnchen-3ya.test",
+                    Router::new().route("/", get(|| async { "münchen apex" })),
+                )
+                .force_https("api")
+                .apex_host("xn--mnchen-3ya.test"),
+        );
+
+    // Test configured punycode hosts match however the request spells the host
+    let resp = test_util::get(&app, "api.xn--mnchen-3ya.test", "/").await;
+    assert_eq!(test_util::text(resp).await, "api");
+    let resp = test_util::send(&app, utf8_host("münchen.test")).await;
+    assert_eq!(test_util::text(resp).await, "münchen apex");
+
+    // Test redirects name the host in punycode
+    let mut req = utf8_host("api.münchen.test");
+    req.headers_mut()
+        .insert("x-forwarded-proto", HeaderValue::from_static("http"));
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(
+        resp.headers()["location"],
+        "https://api.xn--mnchen-3ya.test/"
+    );
+}
@@ -0,0 +1,59 @@
+use axum::{Router, extract::Request, http::StatusCode, routing::get};
+use axum_subdomain_routing::{ApiVersion, SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_ignore_prefixes() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .ignore_prefixes(vec!["WWW".to_string()])
+                .strict(true),
+        );
+
+    for (host, expected) in [
+        // Test the ignored prefix alone routes like the apex
+        ("www.example.com", "Hello from Main App!"),
+        ("example.com", "Hello from Main App!"),
+        // Test the ignored prefix is stripped before lookup
+        ("www.api.example.com", "Hello from API!"),
+        ("api.example.com", "Hello from API!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+
+    // Test only a whole leading label is stripped
+    for host in ["wwwapi.example.com", "api.www.example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND, "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_ignore_prefixes_before_version_label() {
+    let api_router = Router::new().route(
+        "/",
+        get(|req: Request| async move { format!("{:?}", req.extensions().get::<ApiVersion>()) }),
+    );
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("api", api_router)
+            .ignore_prefixes(vec!["www".to_string()])
+            .version_label_prefix(true),
+    );
+
+    // Test the version behind an ignored prefix is still exposed
+    for (host, expected) in [
+        ("www.v2.api.example.com", r#"Some(ApiVersion("v2"))"#),
+        ("v2.api.example.com", r#"Some(ApiVersion("v2"))"#),
+        ("www.api.example.com", "None"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
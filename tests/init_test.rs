@@ -0,0 +1,39 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+use futures::FutureExt;
+
+/// Stand-in for loading tenant routers from a database.
+async fn load_tenants() -> Vec<(String, Router)> {
+    tokio::task::yield_now().await;
+    ["acme", "globex"]
+        .into_iter()
+        .map(|tenant| {
+            let router = Router::new().route("/", get(move || async move { tenant }));
+            (tenant.to_string(), router)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_initialize_registers_loaded_subdomains() {
+    let layer = SubdomainLayer::new()
+        .register("api", Router::new().route("/", get(|| async { "api" })))
+        .on_init(|| load_tenants().boxed());
+    assert!(!layer.contains("acme"));
+
+    let layer = layer.initialize().await;
+    assert_eq!(layer.len(), 3);
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer);
+    for (host, expected) in [
+        ("acme.example.com", "acme"),
+        ("globex.example.com", "globex"),
+        ("api.example.com", "api"),
+        ("initech.example.com", "Hello from Main App!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
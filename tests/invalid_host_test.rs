@@ -0,0 +1,73 @@
+use axum::{
+    Router,
+    body::{Body, to_bytes},
+    http::{HeaderValue, Request, StatusCode},
+    routing::get,
+};
+use axum_subdomain_routing::SubdomainLayer;
+use tower::ServiceExt;
+
+fn request_with_host(host: HeaderValue) -> Request<Body> {
+    Request::builder()
+        .uri("/")
+        .header("Host", host)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn app(layer: SubdomainLayer) -> Router {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer.register("api", api_router))
+}
+
+#[tokio::test]
+async fn test_invalid_host_status() {
+    let app = app(SubdomainLayer::new().invalid_host_status(StatusCode::BAD_REQUEST));
+
+    // Test a non-UTF-8 Host header gets the configured status
+    let host = HeaderValue::from_bytes(b"api.ex\xffample.com").unwrap();
+    let resp = app.clone().oneshot(request_with_host(host)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    assert!(String::from_utf8_lossy(&body).contains("invalid Host header"));
+
+    // Test a missing Host header still falls back
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Test a valid Host header routes normally
+    let host = HeaderValue::from_static("api.example.com");
+    let resp = app.oneshot(request_with_host(host)).await.unwrap();
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, "Hello from API!");
+}
+
+#[tokio::test]
+async fn test_invalid_host_falls_back_by_default() {
+    let app = app(SubdomainLayer::new());
+
+    let host = HeaderValue::from_bytes(b"api.ex\xffample.com").unwrap();
+    let resp = app.oneshot(request_with_host(host)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, "Hello from Main App!");
+}
+
+#[cfg(not(feature = "idna"))]
+#[tokio::test]
+async fn test_invalid_host_message_names_ascii() {
+    let app = app(SubdomainLayer::new().invalid_host_status(StatusCode::BAD_REQUEST));
+
+    // Test valid UTF-8 is still rejected without the idna feature, with an accurate message
+    let host = HeaderValue::from_bytes("api.bücher.com".as_bytes()).unwrap();
+    let resp = app.oneshot(request_with_host(host)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(body, "invalid Host header: not visible ASCII");
+}
@@ -0,0 +1,46 @@
+use axum::{Router, http::StatusCode, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(strict: bool) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .register(
+                    "fe80",
+                    Router::new().route("/", get(|| async { "Hello from fe80!" })),
+                )
+                .strict(strict),
+        )
+}
+
+#[tokio::test]
+async fn test_ipv6_hosts_route_to_main() {
+    let app = app(true);
+
+    // Test bracketed IPv6 hosts have no subdomain, even in strict mode
+    for host in ["[::1]:3000", "[2001:db8::1]", "[fe80::1]:8080", "fe80::1"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(resp.status(), StatusCode::OK, "{host}");
+        assert_eq!(
+            test_util::text(resp).await,
+            "Hello from Main App!",
+            "{host}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_ipv4_hosts_unchanged() {
+    let app = app(false);
+
+    let resp = test_util::get(&app, "127.0.0.1:3000", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+
+    let resp = test_util::get(&app, "api.127.0.0.1:3000", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+}
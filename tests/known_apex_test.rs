@@ -0,0 +1,74 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+fn app(strict: bool) -> Router {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .known_hosts(vec!["example.com".to_string()])
+                .require_known_apex(true)
+                .strict(strict),
+        )
+}
+
+#[tokio::test]
+async fn test_require_known_apex() {
+    let addr = common::serve(app(false)).await;
+    let client = reqwest::Client::new();
+
+    // Test subdomains of the known apex match
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    // Test hosts on other domains fall back
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.otherdomain.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+
+    // Test a suffix without a label boundary is not the known apex
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.notexample.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
+
+#[tokio::test]
+async fn test_require_known_apex_strict() {
+    let addr = common::serve(app(true)).await;
+    let client = reqwest::Client::new();
+
+    // Test hosts on other domains are rejected in strict mode
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.otherdomain.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_multiple_base_domains() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .known_hosts(vec![
+                    "example.com".to_string(),
+                    "example.net".to_string(),
+                    "example.org".to_string(),
+                ])
+                .register("api", api_router)
+                .strict(true),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    for base in ["example.com", "example.net", "example.org"] {
+        // Test api subdomain on every base domain
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", format!("api.{base}"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200, "api.{base}");
+        assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+        // Test apex falls back to main
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", base)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200, "{base}");
+        assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+    }
+}
+
+#[tokio::test]
+async fn test_known_hosts_duplicate_dots() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .known_hosts(vec!["example.com".to_string()])
+                .register("api", api_router)
+                .strict(true),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test duplicate dots are collapsed before known hosts are matched
+    for host in ["api.example..com", "api..example.com"] {
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200, "{host}");
+        assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+    }
+}
+
+#[tokio::test]
+async fn test_known_hosts_label_boundary() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .known_hosts(vec!["example.internal".to_string()])
+                .register("api", Router::new().route("/", get(|| async { "api" })))
+                .register("a.b", Router::new().route("/", get(|| async { "a.b" })))
+                .strict(true),
+        );
+
+    for (host, status, expected) in [
+        // Test the exact known host is the apex, even without a known TLD
+        ("example.internal", 200, "Hello from Main App!"),
+        ("api.example.internal", 200, "api"),
+        ("a.b.example.internal", 200, "a.b"),
+        // Test a host merely ending in the known host's text is not under it
+        ("badexample.internal", 404, ""),
+        ("api.badexample.internal", 404, ""),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(resp.status(), status, "{host}");
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_register_with_builds_once() {
+    let builds = Arc::new(AtomicUsize::new(0));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register_with("api", {
+            let builds = builds.clone();
+            move || {
+                builds.fetch_add(1, Ordering::SeqCst);
+                Router::new().route("/", get(|| async { "Hello from API!" }))
+            }
+        }));
+
+    // Test nothing is built before the first match
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+    assert_eq!(builds.load(Ordering::SeqCst), 0);
+
+    // Test concurrent hits share a single build
+    let hits = (0..32).map(|_| {
+        let app = app.clone();
+        tokio::spawn(async move {
+            let resp = test_util::get(&app, "api.example.com", "/").await;
+            test_util::text(resp).await
+        })
+    });
+    for hit in hits {
+        assert_eq!(hit.await.unwrap(), "Hello from API!");
+    }
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+}
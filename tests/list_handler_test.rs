@@ -0,0 +1,56 @@
+use axum::{
+    Router,
+    body::Body,
+    extract::Request,
+    http::{StatusCode, header},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, routing_list_handler, test_util};
+
+fn app() -> Router {
+    let layer = SubdomainLayer::new()
+        .register("api", Router::new())
+        .register("admin", Router::new())
+        .register("*", Router::new());
+
+    Router::new()
+        .route("/routes", get(routing_list_handler(&layer, "s3cret")))
+        .layer(layer)
+}
+
+fn request(authorization: Option<&str>) -> Request {
+    let mut req = Request::builder()
+        .uri("/routes")
+        .header(header::HOST, "example.com");
+    if let Some(authorization) = authorization {
+        req = req.header(header::AUTHORIZATION, authorization);
+    }
+    req.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_routing_list_handler_authorized() {
+    let resp = test_util::send(&app(), request(Some("Bearer s3cret"))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+
+    let list: Vec<String> = serde_json::from_str(&test_util::text(resp).await).unwrap();
+    assert_eq!(list, ["*", "admin", "api"]);
+}
+
+#[tokio::test]
+async fn test_routing_list_handler_unauthorized() {
+    let app = app();
+
+    // Test missing, wrong and malformed credentials are all rejected
+    for authorization in [
+        None,
+        Some("Bearer wrong"),
+        Some("Bearer s3cre"),
+        Some("s3cret"),
+    ] {
+        let resp = test_util::send(&app, request(authorization)).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED, "{authorization:?}");
+        assert_eq!(resp.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    }
+}
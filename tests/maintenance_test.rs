@@ -0,0 +1,80 @@
+mod common;
+
+use axum::{
+    Router,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use axum_subdomain_routing::SubdomainLayer;
+
+fn maintenance_page() -> Response {
+    (StatusCode::SERVICE_UNAVAILABLE, "Down for maintenance").into_response()
+}
+
+fn app(enabled: bool) -> Router {
+    let api_router = Router::new()
+        .route("/", get(|| async { "Hello from API!" }))
+        .route("/healthz", get(|| async { "API healthy" }));
+
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .route("/healthz", get(|| async { "healthy" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .global_maintenance(enabled, maintenance_page)
+                .maintenance_allowlist(vec!["/healthz".to_string()]),
+        )
+}
+
+#[tokio::test]
+async fn test_global_maintenance() {
+    let addr = common::serve(app(true)).await;
+    let client = reqwest::Client::new();
+
+    // Test every host gets the maintenance page
+    for host in ["example.com", "api.example.com", "unknown.example.com"] {
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 503, "{host}");
+        assert_eq!(resp.text().await.unwrap(), "Down for maintenance");
+    }
+
+    // Test the allow-listed health path is still routed
+    let resp = client
+        .get(format!("http://{}/healthz", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "healthy");
+
+    let resp = client
+        .get(format!("http://{}/healthz", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "API healthy");
+}
+
+#[tokio::test]
+async fn test_global_maintenance_disabled() {
+    let addr = common::serve(app(false)).await;
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
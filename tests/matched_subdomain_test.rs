@@ -0,0 +1,43 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{MatchedSubdomain, SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_matched_subdomain_extractor() {
+    let tenant_router = Router::new().route(
+        "/",
+        get(|MatchedSubdomain(tenant): MatchedSubdomain| async move { format!("tenant {tenant}") }),
+    );
+
+    let app = Router::new()
+        .route(
+            "/",
+            get(|sub: Option<MatchedSubdomain>| async move { format!("main {sub:?}") }),
+        )
+        .route(
+            "/required",
+            get(|MatchedSubdomain(sub): MatchedSubdomain| async move { sub }),
+        )
+        .layer(
+            SubdomainLayer::new()
+                .register("*", tenant_router)
+                .register("www", Router::new()),
+        );
+
+    // Test the wildcard router sees the actual tenant
+    for tenant in ["acme", "globex"] {
+        let resp = test_util::get(&app, &format!("{tenant}.example.com"), "/").await;
+        assert_eq!(test_util::text(resp).await, format!("tenant {tenant}"));
+    }
+
+    // Test the main router has no matched subdomain
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "main None");
+
+    // Test the required extractor rejects when missing
+    let resp = test_util::get(&app, "example.com", "/required").await;
+    assert_eq!(resp.status(), 500);
+    assert_eq!(
+        test_util::text(resp).await,
+        "request was not routed to a subdomain router"
+    );
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use axum::{
+    Router,
+    http::Method,
+    routing::{get, post},
+};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_allowed_methods() {
+    let cdn_router = Router::new().route(
+        "/",
+        get(|| async { "Hello from CDN!" }).post(|| async { "uploaded" }),
+    );
+    let api_router = Router::new().route("/", post(|| async { "created" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("cdn", cdn_router)
+                .register("api", api_router)
+                .allowed_methods("cdn", &[Method::GET, Method::HEAD]),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test a disallowed method is rejected with the Allow header
+    let resp = client
+        .post(format!("http://{}", addr))
+        .header("Host", "cdn.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 405);
+    assert_eq!(resp.headers().get("allow").unwrap(), "GET, HEAD");
+
+    // Test allowed methods are routed
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "cdn.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "Hello from CDN!");
+
+    let resp = client
+        .head(format!("http://{}", addr))
+        .header("Host", "cdn.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Test other subdomains are unrestricted
+    let resp = client
+        .post(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "created");
+}
@@ -0,0 +1,80 @@
+use axum::{Router, body::Body, http::Request, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn request(accept: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .uri("/")
+        .header("host", "api.example.com");
+    if let Some(accept) = accept {
+        builder = builder.header("accept", accept);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_register_negotiated() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("api", Router::new().route("/", get(|| async { "v1" })))
+            .register_negotiated(
+                "api",
+                "application/vnd.v2+json",
+                Router::new().route("/", get(|| async { "v2" })),
+            ),
+    );
+
+    // Test the negotiated router is selected by the Accept media type
+    let resp = test_util::send(&app, request(Some("application/vnd.v2+json"))).await;
+    assert_eq!(test_util::text(resp).await, "v2");
+
+    let resp = test_util::send(
+        &app,
+        request(Some("text/html, application/vnd.v2+json;q=0.9")),
+    )
+    .await;
+    assert_eq!(test_util::text(resp).await, "v2");
+
+    // Test other or missing Accept headers use the base router
+    let resp = test_util::send(&app, request(Some("application/json"))).await;
+    assert_eq!(test_util::text(resp).await, "v1");
+
+    let resp = test_util::send(&app, request(None)).await;
+    assert_eq!(test_util::text(resp).await, "v1");
+}
+
+#[tokio::test]
+async fn test_register_negotiated_weights() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("api", Router::new().route("/", get(|| async { "v1" })))
+            .register_negotiated(
+                "api",
+                "application/json",
+                Router::new().route("/", get(|| async { "json" })),
+            )
+            .register_negotiated(
+                "api",
+                "application/vnd.v2+json",
+                Router::new().route("/", get(|| async { "v2" })),
+            ),
+    );
+
+    for (accept, expected) in [
+        // Test a media type weighted zero is never selected
+        ("application/vnd.v2+json;q=0", "v1"),
+        // Test heavier media types win over the client's listing order
+        ("application/json;q=0.1, application/vnd.v2+json", "v2"),
+        (
+            "application/vnd.v2+json; Q=0.5, application/json;q=0.8",
+            "json",
+        ),
+        // Test equal weights keep the client's order
+        (
+            "application/json;q=0.5, application/vnd.v2+json;q=0.5",
+            "json",
+        ),
+    ] {
+        let resp = test_util::send(&app, request(Some(accept))).await;
+        assert_eq!(test_util::text(resp).await, expected, "{accept}");
+    }
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use axum::{
+    Extension, Router,
+    extract::Request,
+    middleware::{self, Next},
+    routing::get,
+};
+use axum_subdomain_routing::{MatchedSubdomain, OriginalHost, OuterSubdomains, SubdomainLayer};
+
+#[derive(Clone)]
+struct OuterTag(&'static str);
+
+#[derive(Clone)]
+struct InnerTag(&'static str);
+
+#[tokio::test]
+async fn test_nested_subdomain_layers() {
+    // Second stage: routes `api` under the `eu.example.com` region
+    let regional_api =
+        Router::new()
+            .route(
+                "/",
+                get(
+                    |Extension(outer): Extension<OuterTag>,
+                     Extension(inner): Extension<InnerTag>| async move {
+                        format!("Hello from EU API! ({} + {})", outer.0, inner.0)
+                    },
+                ),
+            )
+            .route(
+                "/matched",
+                get(
+                    |MatchedSubdomain(matched): MatchedSubdomain,
+                     Extension(OuterSubdomains(outer)): Extension<OuterSubdomains>,
+                     Extension(host): Extension<OriginalHost>| async move {
+                        format!("{matched} in {outer:?} on {}", host.raw)
+                    },
+                ),
+            );
+    let eu_router = Router::new()
+        .route("/", get(|| async { "Hello from EU!" }))
+        .layer(
+            SubdomainLayer::new()
+                .known_hosts(vec!["eu.example.com".to_string()])
+                .register("api", regional_api),
+        )
+        .layer(middleware::from_fn(|mut req: Request, next: Next| async {
+            req.extensions_mut().insert(InnerTag("inner"));
+            next.run(req).await
+        }));
+
+    // First stage: routes the region
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("eu", eu_router.clone())
+                .register("api.eu", eu_router),
+        )
+        .layer(middleware::from_fn(|mut req: Request, next: Next| async {
+            req.extensions_mut().insert(OuterTag("outer"));
+            next.run(req).await
+        }));
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test both stages route and extensions from both are present
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.eu.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.text().await.unwrap(),
+        "Hello from EU API! (outer + inner)"
+    );
+
+    // Test the layers' own extensions survive: the inner match and the outer one it replaced
+    let resp = client
+        .get(format!("http://{}/matched", addr))
+        .header("Host", "api.eu.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.text().await.unwrap(),
+        r#"api in ["api.eu"] on api.eu.example.com"#
+    );
+
+    // Test the region apex is served by the first stage
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "eu.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from EU!");
+
+    // Test the main apex is untouched
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
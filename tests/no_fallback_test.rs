@@ -0,0 +1,51 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_no_fallback() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .no_fallback(),
+        );
+
+    // Test matched subdomains are routed
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+
+    // Test unmatched subdomains and the apex never reach the main router
+    for host in ["www.example.com", "example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND, "{host}");
+    }
+
+    // Test requests without a host 404 too
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_no_fallback_keeps_apex_router() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register_apex_and_subdomain("www", Router::new().route("/", get(|| async { "apex" })))
+            .no_fallback(),
+    );
+
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "apex");
+
+    let resp = test_util::get(&app, "blog.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use axum::{Router, http::Method, routing::get};
+use axum_subdomain_routing::{CorsConfig, RequestInfo, SubdomainLayer, test_util};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_on_request_reports_routing() {
+    let api_router = Router::new().route("/users", get(|| async { "Hello from API!" }));
+    let seen: Arc<Mutex<Vec<RequestInfo>>> = Arc::default();
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .on_request({
+                    let seen = seen.clone();
+                    move |info| seen.lock().unwrap().push(info.clone())
+                }),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test matched request
+    client
+        .get(format!("http://{}/users", addr))
+        .header("Host", format!("api.example.com:{}", addr.port()))
+        .send()
+        .await
+        .unwrap();
+
+    // Test unmatched request
+    client
+        .get(format!("http://{}/", addr))
+        .header("Host", "unknown.example.com")
+        .send()
+        .await
+        .unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+
+    let matched = &seen[0];
+    assert_eq!(
+        matched.raw_host.as_deref(),
+        Some(format!("api.example.com:{}", addr.port()).as_str())
+    );
+    assert_eq!(matched.subdomain.as_deref(), Some("api"));
+    assert_eq!(matched.matched.as_deref(), Some("api"));
+    assert_eq!(matched.method, Method::GET);
+    assert_eq!(matched.path, "/users");
+
+    let unmatched = &seen[1];
+    assert_eq!(unmatched.raw_host.as_deref(), Some("unknown.example.com"));
+    assert_eq!(unmatched.subdomain.as_deref(), Some("unknown"));
+    assert_eq!(unmatched.matched, None);
+    assert_eq!(unmatched.path, "/");
+}
+
+#[tokio::test]
+async fn test_on_request_reports_early_responses() {
+    let seen: Arc<Mutex<Vec<RequestInfo>>> = Arc::default();
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .route("/health", get(|| async { "ok" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", Router::new())
+                .global_preflight(CorsConfig::new())
+                .exempt_paths(vec!["/health".to_string()])
+                .readiness("/readyz", || true)
+                .on_request({
+                    let seen = seen.clone();
+                    move |info| seen.lock().unwrap().push(info.clone())
+                }),
+        );
+
+    let preflight = axum::http::Request::builder()
+        .method(Method::OPTIONS)
+        .uri("/")
+        .header("host", "api.example.com")
+        .header("access-control-request-method", "POST")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    test_util::send(&app, preflight).await;
+    test_util::get(&app, "api.example.com", "/health").await;
+    test_util::get(&app, "api.example.com", "/readyz").await;
+
+    // Test each request is reported once, without a subdomain
+    let seen = seen.lock().unwrap();
+    let paths: Vec<&str> = seen.iter().map(|info| info.path.as_str()).collect();
+    assert_eq!(paths, ["/", "/health", "/readyz"]);
+    assert!(seen.iter().all(|info| info.subdomain.is_none()));
+}
@@ -0,0 +1,61 @@
+//! The routing scenarios of `integration_test.rs`, driven through `test_util` without sockets.
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(layer: SubdomainLayer) -> Router {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+    let admin_router = Router::new().route("/", get(|| async { "Hello from Admin!" }));
+    let sub_api_router = Router::new().route("/", get(|| async { "Hello from Sub API!" }));
+
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            layer
+                .register("api", api_router)
+                .register("admin", admin_router)
+                .register("sub.api", sub_api_router),
+        )
+}
+
+async fn assert_text(app: &Router, host: &str, expected: &str) {
+    let resp = test_util::get(app, host, "/").await;
+    assert_eq!(resp.status(), StatusCode::OK, "{host}");
+    assert_eq!(test_util::text(resp).await, expected, "{host}");
+}
+
+#[tokio::test]
+async fn test_subdomain_routing() {
+    let app = app(SubdomainLayer::new());
+
+    for base in ["127.0.0.1:3000", "localhost:3000", "example.com:3000"] {
+        // Test main app
+        assert_text(&app, base, "Hello from Main App!").await;
+
+        // Test registered subdomains
+        assert_text(&app, &format!("api.{base}"), "Hello from API!").await;
+        assert_text(&app, &format!("admin.{base}"), "Hello from Admin!").await;
+        assert_text(&app, &format!("sub.api.{base}"), "Hello from Sub API!").await;
+
+        // Test unknown subdomain falls back to main
+        assert_text(&app, &format!("unknown.{base}"), "Hello from Main App!").await;
+    }
+}
+
+#[tokio::test]
+async fn test_subdomain_routing_strict() {
+    let app = app(SubdomainLayer::new().strict(true));
+
+    for base in ["127.0.0.1:3000", "localhost:3000", "example.com:3000"] {
+        // Test main app still works
+        assert_text(&app, base, "Hello from Main App!").await;
+
+        // Test registered subdomains
+        assert_text(&app, &format!("api.{base}"), "Hello from API!").await;
+        assert_text(&app, &format!("admin.{base}"), "Hello from Admin!").await;
+
+        // Test unknown subdomain returns 404 in strict mode
+        let resp = test_util::get(&app, &format!("unknown.{base}"), "/").await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
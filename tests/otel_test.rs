@@ -0,0 +1,83 @@
+#![cfg(feature = "otel")]
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use axum_subdomain_routing::SubdomainLayer;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tower::ServiceExt;
+use tracing::{
+    Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{Layer, layer::Context, prelude::*, registry::LookupSpan};
+
+type Fields = HashMap<String, String>;
+
+/// Records the fields of every `subdomain.dispatch` span.
+#[derive(Clone, Default)]
+struct SpanRecorder(Arc<Mutex<HashMap<Id, Fields>>>);
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanRecorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        if attrs.metadata().name() == "subdomain.dispatch" {
+            let mut fields = Fields::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            self.0.lock().unwrap().insert(id.clone(), fields);
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        if let Some(fields) = self.0.lock().unwrap().get_mut(id) {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_otel_attributes_on_dispatch_span() {
+    let recorder = SpanRecorder::default();
+    let _guard = tracing_subscriber::registry()
+        .with(recorder.clone())
+        .set_default();
+
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register("api", api_router));
+
+    let req = Request::builder()
+        .uri("/")
+        .header("Host", "api.example.com:8080")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let spans = recorder.0.lock().unwrap();
+    assert_eq!(spans.len(), 1);
+    let fields = spans.values().next().unwrap();
+    assert_eq!(fields["http.host"], "api.example.com:8080");
+    assert_eq!(fields["server.address"], "api.example.com");
+    assert_eq!(fields["subdomain"], "api");
+}
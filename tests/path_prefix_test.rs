@@ -0,0 +1,80 @@
+mod common;
+
+use axum::{
+    Router,
+    extract::{Path, Request},
+    routing::get,
+};
+use axum_subdomain_routing::SubdomainLayer;
+use tower::Layer;
+
+#[tokio::test]
+async fn test_path_prefix_template() {
+    let api_router = Router::new().route("/info", get(|| async { "Hello from API!" }));
+
+    let main_router = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .route(
+            "/t/{tenant}",
+            get(|Path(tenant): Path<String>| async move { format!("Tenant {tenant} home") }),
+        )
+        .route(
+            "/t/{tenant}/info",
+            get(|Path(tenant): Path<String>, req: Request| async move {
+                format!("Tenant {tenant}: {}", req.uri())
+            }),
+        );
+
+    // Wrap the whole router so the rewritten path is routed again
+    let layer = SubdomainLayer::new()
+        .register("api", api_router)
+        .path_prefix_template("/t/{subdomain}");
+    let app = Router::new().fallback_service(layer.layer(main_router));
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the prefix is expanded and prepended, keeping the query
+    let resp = client
+        .get(format!("http://{}/info?tab=1", addr))
+        .header("Host", "acme.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.text().await.unwrap(),
+        "Tenant acme: /t/acme/info?tab=1"
+    );
+
+    // Test the root path maps to the prefix itself
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "acme.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Tenant acme home");
+
+    // Test matched subdomains and the apex are unaffected
+    let resp = client
+        .get(format!("http://{}/info", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
+
+#[test]
+#[should_panic(expected = "must contain `{subdomain}`")]
+fn test_path_prefix_template_requires_placeholder() {
+    let _ = SubdomainLayer::new().path_prefix_template("/tenants");
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::OriginalUri,
+    http::{HeaderMap, HeaderName, Method, Request, Uri},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_path_tenant_fallback() {
+    let tenant_router = Router::new().route(
+        "/info",
+        get(|uri: Uri| async move { format!("Hello from Acme! ({uri})") }),
+    );
+
+    let app = Router::new()
+        .route(
+            "/{*path}",
+            get(|OriginalUri(uri): OriginalUri| async move { format!("Main App: {uri}") }),
+        )
+        .layer(
+            SubdomainLayer::new()
+                .register("acme", tenant_router)
+                .path_tenant_fallback(true),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the first segment selects the tenant and is stripped
+    let resp = client
+        .get(format!("http://{}/acme/info?page=2", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.text().await.unwrap(),
+        "Hello from Acme! (/info?page=2)"
+    );
+
+    // Test the subdomain form still works
+    let resp = client
+        .get(format!("http://{}/info", addr))
+        .header("Host", "acme.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Acme! (/info)");
+
+    // Test unknown segments fall back unmodified
+    let resp = client
+        .get(format!("http://{}/other/info", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Main App: /other/info");
+}
+
+#[tokio::test]
+async fn test_path_tenant_normalizes_and_skips_wildcards() {
+    let app = Router::new()
+        .route("/{*path}", get(|| async { "Main App" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "acme",
+                    Router::new().route("/info", get(|| async { "Acme" })),
+                )
+                .register(
+                    "*",
+                    Router::new().route("/info", get(|| async { "wildcard" })),
+                )
+                .path_tenant_fallback(true),
+        );
+
+    // Test the segment matches case-insensitively
+    let resp = test_util::get(&app, "example.com", "/ACME/info").await;
+    assert_eq!(test_util::text(resp).await, "Acme");
+
+    // Test a wildcard does not claim arbitrary segments
+    let resp = test_util::get(&app, "example.com", "/assets/info").await;
+    assert_eq!(test_util::text(resp).await, "Main App");
+}
+
+#[tokio::test]
+async fn test_path_tenant_applies_subdomain_policies() {
+    let token =
+        |headers: HeaderMap| async move { format!("token={:?}", headers.get("x-internal-token")) };
+    let app = Router::new()
+        .route("/{*path}", get(|| async { "Main App" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("acme", Router::new().route("/info", get(token).post(token)))
+                .strip_request_headers("acme", &[HeaderName::from_static("x-internal-token")])
+                .enforce_same_origin("acme")
+                .allowed_methods("acme", &[Method::GET])
+                .path_tenant_fallback(true),
+        );
+
+    // Test the tenant's method restriction holds on the path form
+    let req = Request::builder()
+        .method("POST")
+        .uri("/acme/info")
+        .header("host", "example.com")
+        .header("origin", "https://evil.example.org")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(test_util::send(&app, req).await.status(), 405);
+
+    // Test the tenant's headers are stripped on the path form
+    let req = Request::builder()
+        .uri("/acme/info")
+        .header("host", "example.com")
+        .header("x-internal-token", "secret")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        test_util::text(test_util::send(&app, req).await).await,
+        "token=None"
+    );
+}
+
+#[tokio::test]
+async fn test_path_tenant_skips_patterns() {
+    let app = Router::new()
+        .route("/{*path}", get(|| async { "Main App" }))
+        .layer(
+            SubdomainLayer::new()
+                .register_regex(
+                    regex::Regex::new(".+").unwrap(),
+                    Router::new().route("/", get(|| async { "pattern" })),
+                )
+                .path_tenant_fallback(true),
+        );
+
+    let resp = test_util::get(&app, "example.com", "/favicon.ico").await;
+    assert_eq!(test_util::text(resp).await, "Main App");
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use axum::{Router, body::Body, http::Request, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_register_on_ports() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register_on_ports("api", &[8443, 9443], api_router));
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test listed ports route to the subdomain
+    for host in ["api.example.com:8443", "api.example.com:9443"] {
+        let resp = client
+            .get(format!("http://{}/", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+    }
+
+    // Test other ports, including the implicit default, fall back
+    for host in ["api.example.com:80", "api.example.com"] {
+        let resp = client
+            .get(format!("http://{}/", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+    }
+}
+
+#[tokio::test]
+async fn test_register_on_ports_forwarded_host() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register_on_ports(
+                    "api",
+                    &[8443],
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .trust_forwarded_host(true),
+        );
+
+    // Test the port comes from the trusted forwarded host, not the proxy's Host
+    let req = Request::builder()
+        .uri("/")
+        .header("host", "internal:80")
+        .header("x-forwarded-host", "api.example.com:8443")
+        .body(Body::empty())
+        .unwrap();
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{
+    Router,
+    http::{HeaderName, Method},
+    routing::get,
+};
+use axum_subdomain_routing::{CorsConfig, SubdomainLayer};
+
+#[tokio::test]
+async fn test_global_preflight() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let cors = CorsConfig::new()
+        .allow_origin("https://app.example.com")
+        .allow_methods(&[Method::GET, Method::POST])
+        .allow_headers(&[HeaderName::from_static("content-type")])
+        .max_age(Duration::from_secs(600));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .strict(true)
+                .global_preflight(cors),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test matched, unmatched and apex hosts all answer the preflight the same way
+    for host in ["api.example.com", "unknown.example.com", "example.com"] {
+        let resp = client
+            .request(Method::OPTIONS, format!("http://{}/", addr))
+            .header("Host", host)
+            .header("Origin", "https://app.example.com")
+            .header("Access-Control-Request-Method", "POST")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 204);
+        let headers = resp.headers();
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+        assert_eq!(headers["access-control-allow-methods"], "GET, POST");
+        assert_eq!(headers["access-control-allow-headers"], "content-type");
+        assert_eq!(headers["access-control-max-age"], "600");
+    }
+
+    // Test an origin outside the policy is not echoed
+    let resp = client
+        .request(Method::OPTIONS, format!("http://{}/", addr))
+        .header("Host", "api.example.com")
+        .header("Origin", "https://evil.example.org")
+        .header("Access-Control-Request-Method", "POST")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 204);
+    assert!(!resp.headers().contains_key("access-control-allow-origin"));
+
+    // Test requests that are not preflights are routed as usual
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "proxy")]
+
+mod common;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::Request,
+    http::{HeaderMap, Version},
+    routing::{get, post},
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+#[tokio::test]
+async fn test_register_proxy() {
+    let upstream = Router::new()
+        .route(
+            "/app/x",
+            get(|headers: HeaderMap, req: Request| async move {
+                let host = headers["host"].to_str().unwrap().to_string();
+                (
+                    [("x-upstream", "yes")],
+                    format!("upstream {} via {host}", req.uri()),
+                )
+            }),
+        )
+        .route("/app/echo", post(|body: String| async move { body }));
+    let upstream_addr = common::serve(upstream).await;
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new().register_proxy("proxy", &format!("http://{upstream_addr}/app")),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the request is forwarded with the path, query and upstream Host
+    let resp = client
+        .get(format!("http://{}/x?page=2", addr))
+        .header("Host", "proxy.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["x-upstream"], "yes");
+    assert_eq!(
+        resp.text().await.unwrap(),
+        format!("upstream /app/x?page=2 via {upstream_addr}")
+    );
+
+    // Test request bodies are relayed
+    let resp = client
+        .post(format!("http://{}/echo", addr))
+        .header("Host", "proxy.example.com")
+        .body("ping")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "ping");
+
+    // Test other hosts are not proxied
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
+
+#[tokio::test]
+async fn test_register_proxy_unreachable_upstream() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let dead_addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let app = Router::new()
+        .layer(SubdomainLayer::new().register_proxy("proxy", &format!("http://{dead_addr}")));
+    let addr = common::serve(app).await;
+
+    // Test an unreachable upstream yields 502
+    let resp = reqwest::Client::new()
+        .get(format!("http://{}/", addr))
+        .header("Host", "proxy.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 502);
+}
+
+/// A listener that counts accepted connections.
+struct CountingListener {
+    listener: tokio::net::TcpListener,
+    accepted: Arc<AtomicUsize>,
+}
+
+impl axum::serve::Listener for CountingListener {
+    type Io = tokio::net::TcpStream;
+    type Addr = std::net::SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        let accepted = self.listener.accept().await;
+        self.accepted.fetch_add(1, Ordering::SeqCst);
+        accepted.unwrap()
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        self.listener.local_addr()
+    }
+}
+
+#[tokio::test]
+async fn test_warm_upstreams() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let upstream_addr = listener.local_addr().unwrap();
+    let accepted = Arc::new(AtomicUsize::new(0));
+    let listener = CountingListener {
+        listener,
+        accepted: accepted.clone(),
+    };
+    let upstream = Router::new().route("/", get(|| async { "upstream" }));
+    tokio::spawn(async move { axum::serve(listener, upstream).await.unwrap() });
+
+    let layer = SubdomainLayer::new().register_proxy("proxy", &format!("http://{upstream_addr}"));
+
+    // Test the connection is established before any proxied request
+    assert_eq!(layer.warm_upstreams().await, 1);
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+
+    // Test the first proxied request reuses the warmed connection
+    let app = Router::new().layer(layer);
+    let resp = test_util::get(&app, "proxy.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "upstream");
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_register_proxy_http2_request() {
+    let upstream = Router::new().route("/", get(|| async { "upstream" }));
+    let upstream_addr = common::serve(upstream).await;
+
+    let app = Router::new()
+        .layer(SubdomainLayer::new().register_proxy("proxy", &format!("http://{upstream_addr}")));
+
+    // Test an HTTP/2 request is forwarded over the HTTP/1 upstream connection
+    let req = Request::builder()
+        .version(Version::HTTP_2)
+        .uri("https://proxy.example.com/")
+        .body(Body::empty())
+        .unwrap();
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test_util::text(resp).await, "upstream");
+}
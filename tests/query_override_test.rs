@@ -0,0 +1,51 @@
+#![cfg(feature = "query-override")]
+
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_query_override_param() {
+    let admin_router = Router::new().route("/", get(|| async { "Hello from Admin!" }));
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("admin", admin_router)
+                .register("api", api_router)
+                .query_override_param("__subdomain"),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the override routes the apex to a subdomain
+    let resp = client
+        .get(format!("http://{}/?__subdomain=admin", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Admin!");
+
+    // Test the override wins over the host's subdomain
+    let resp = client
+        .get(format!("http://{}/?page=1&__subdomain=admin", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Admin!");
+
+    // Test requests without the parameter are unaffected
+    let resp = client
+        .get(format!("http://{}/?page=1", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
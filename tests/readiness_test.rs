@@ -0,0 +1,113 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+#[tokio::test]
+async fn test_readiness_reflects_check() {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+    let ready = Arc::new(AtomicBool::new(false));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .readiness("/readyz", {
+                    let ready = ready.clone();
+                    move || ready.load(Ordering::SeqCst)
+                }),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test not ready on any host
+    for host in ["example.com", "api.example.com"] {
+        let resp = client
+            .get(format!("http://{}/readyz", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 503);
+    }
+
+    ready.store(true, Ordering::SeqCst);
+
+    // Test ready on any host
+    for host in ["example.com", "api.example.com"] {
+        let resp = client
+            .get(format!("http://{}/readyz", addr))
+            .header("Host", host)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    // Test other paths are routed normally
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
+
+#[tokio::test]
+async fn test_readiness_requires_registered_subdomain() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().readiness("/readyz", || true));
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(format!("http://{}/readyz", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 503);
+}
+
+#[tokio::test]
+async fn test_readiness_counts_pattern_and_host_routes() {
+    let router = || Router::new().route("/", get(|| async { "routed" }));
+    for layer in [
+        SubdomainLayer::new().register_regex(regex::Regex::new(r"user-\d+").unwrap(), router()),
+        SubdomainLayer::new().register_host("example.org", router()),
+    ] {
+        let app = Router::new().layer(layer.readiness("/readyz", || true));
+        let resp = test_util::get(&app, "example.com", "/readyz").await;
+        assert_eq!(resp.status(), 200);
+    }
+}
+
+#[tokio::test]
+async fn test_readiness_with_catch_all() {
+    let catch_all = Router::new().fallback(|| async { "catch-all" });
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("api", Router::new())
+            .catch_all(catch_all)
+            .readiness("/readyz", || true),
+    );
+
+    // Test the probe is answered rather than forwarded to the catch-all
+    for host in ["example.com", "api.example.com"] {
+        let resp = test_util::get(&app, host, "/readyz").await;
+        assert_eq!(resp.status(), 200, "{host}");
+        assert_eq!(test_util::text(resp).await, "", "{host}");
+    }
+
+    let resp = test_util::get(&app, "api.example.com", "/other").await;
+    assert_eq!(test_util::text(resp).await, "catch-all");
+}
@@ -0,0 +1,33 @@
+use axum::{Router, http::StatusCode, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_gate_until_ready() {
+    let layer = SubdomainLayer::new()
+        .register(
+            "api",
+            Router::new().route("/", get(|| async { "Hello from API!" })),
+        )
+        .readiness("/ready", || true)
+        .gate_until_ready();
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer.clone());
+
+    // Test subdomain requests are rejected before the layer is ready
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    // Test apex and readiness requests are still answered
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+    let resp = test_util::get(&app, "example.com", "/ready").await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    // Test marking a clone ready opens the gate for the running service
+    layer.mark_ready();
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+    let resp = test_util::get(&app, "example.com", "/ready").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
@@ -0,0 +1,102 @@
+use axum::{Extension, Router, routing::get};
+use axum_subdomain_routing::{SubdomainCaptures, SubdomainLayer, test_util};
+use regex::{Regex, RegexBuilder};
+
+fn app() -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "user-admin",
+                    Router::new().route("/", get(|| async { "exact" })),
+                )
+                .register_regex(
+                    Regex::new(r"user-(?<id>\d+)").unwrap(),
+                    Router::new().route(
+                        "/",
+                        get(
+                            |Extension(captures): Extension<SubdomainCaptures>| async move {
+                                format!(
+                                    "user {} ({})",
+                                    captures.name("id").unwrap(),
+                                    captures.get(0).unwrap()
+                                )
+                            },
+                        ),
+                    ),
+                )
+                .register_regex(
+                    Regex::new(r"user-\w+").unwrap(),
+                    Router::new().route("/", get(|| async { "any user" })),
+                )
+                .register_regex(
+                    Regex::new(r"pr-[0-9]+-preview").unwrap(),
+                    Router::new().route("/", get(|| async { "preview" })),
+                ),
+        )
+}
+
+#[tokio::test]
+async fn test_register_regex_captures_and_order() {
+    let app = app();
+
+    for (host, expected) in [
+        // Test captures are exposed to the handler
+        ("user-42.example.com", "user 42 (user-42)"),
+        // Test the first matching pattern wins, and later ones still match
+        ("user-bob.example.com", "any user"),
+        ("pr-17-preview.example.com", "preview"),
+        // Test exact registrations take precedence over patterns
+        ("user-admin.example.com", "exact"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_register_regex_fallthrough() {
+    let app = app();
+
+    // Test patterns must match the whole subdomain
+    for host in [
+        "pr-17-preview-old.example.com",
+        "old-pr-17-preview.example.com",
+        "xuser-1.example.com",
+        "www.example.com",
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(
+            test_util::text(resp).await,
+            "Hello from Main App!",
+            "{host}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_register_regex_keeps_builder_options() {
+    let regex = RegexBuilder::new(r"user - (?<id> \d+ )  # the numeric id")
+        .ignore_whitespace(true)
+        .build()
+        .unwrap();
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register_regex(
+            regex,
+            Router::new().route(
+                "/",
+                get(
+                    |Extension(captures): Extension<SubdomainCaptures>| async move {
+                        format!("user {}", captures.name("id").unwrap())
+                    },
+                ),
+            ),
+        ));
+
+    let resp = test_util::get(&app, "user-42.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "user 42");
+    let resp = test_util::get(&app, "user-42x.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainError, SubdomainLayer, test_util};
+use std::{borrow::Cow, collections::HashMap};
+
+#[tokio::test]
+async fn test_register_key_types() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("str", Router::new().route("/", get(|| async { "str" })))
+                .register(
+                    String::from("string"),
+                    Router::new().route("/", get(|| async { "string" })),
+                )
+                .register(
+                    Cow::Borrowed("borrowed"),
+                    Router::new().route("/", get(|| async { "borrowed" })),
+                )
+                .register(
+                    Cow::<str>::Owned("owned".to_string()),
+                    Router::new().route("/", get(|| async { "owned" })),
+                ),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    for sub in ["str", "string", "borrowed", "owned"] {
+        let resp = client
+            .get(format!("http://{}", addr))
+            .header("Host", format!("{sub}.example.com"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), sub);
+    }
+}
+
+#[tokio::test]
+async fn test_normalize_keys_modes() {
+    for normalize in [true, false] {
+        let app = Router::new()
+            .route("/", get(|| async { "Hello from Main App!" }))
+            .layer(
+                SubdomainLayer::new()
+                    .normalize_keys(normalize)
+                    .register("api", Router::new().route("/", get(|| async { "api" })))
+                    .register(
+                        "sub.api",
+                        Router::new().route("/", get(|| async { "sub.api" })),
+                    ),
+            );
+
+        let addr = common::serve(app).await;
+        let client = reqwest::Client::new();
+
+        for sub in ["api", "sub.api"] {
+            let resp = client
+                .get(format!("http://{}", addr))
+                .header("Host", format!("{sub}.example.com"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(
+                resp.text().await.unwrap(),
+                sub,
+                "normalize_keys({normalize})"
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_normalize_keys_trims() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register(
+            " api. ",
+            Router::new().route("/", get(|| async { "Hello from API!" })),
+        ));
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+}
+
+#[test]
+fn test_len_and_is_empty() {
+    let layer = SubdomainLayer::new();
+    assert!(layer.is_empty());
+    assert_eq!(layer.len(), 0);
+
+    let layer = layer
+        .register("api", Router::new())
+        .register("admin", Router::new())
+        .register("sub.api", Router::new());
+    assert!(!layer.is_empty());
+    assert_eq!(layer.len(), 3);
+
+    // Test re-registering a key replaces it
+    let layer = layer.register("api", Router::new());
+    assert_eq!(layer.len(), 3);
+}
+
+#[test]
+fn test_try_register_rejects_pattern_keys() {
+    for key in ["api-*", "*api", "api-[0-9]+"] {
+        let err = SubdomainLayer::new()
+            .try_register(key, Router::new())
+            .err()
+            .unwrap();
+        assert_eq!(err, SubdomainError::PatternKey(key.to_string()));
+        assert!(err.to_string().contains("matched literally"));
+    }
+
+    let err = SubdomainLayer::new()
+        .try_register(" . ", Router::new())
+        .err()
+        .unwrap();
+    assert_eq!(err, SubdomainError::EmptySubdomain);
+
+    let layer = SubdomainLayer::new()
+        .try_register("api", Router::new())
+        .unwrap()
+        .try_register("*", Router::new())
+        .unwrap()
+        .try_register("*.api", Router::new())
+        .unwrap();
+    assert_eq!(layer.len(), 1);
+}
+
+#[test]
+fn test_subdomains_and_contains() {
+    let layer = SubdomainLayer::new()
+        .register("api", Router::new())
+        .register("admin", Router::new())
+        .register("sub.api", Router::new());
+
+    let mut subdomains = layer.subdomains();
+    subdomains.sort_unstable();
+    assert_eq!(subdomains, ["admin", "api", "sub.api"]);
+
+    assert!(layer.contains("api"));
+    assert!(layer.contains(" Sub.Api. "));
+    assert!(!layer.contains("www"));
+}
+
+#[test]
+fn test_subdomains_skip_wildcard_and_alpn_keys() {
+    let layer = SubdomainLayer::new()
+        .register("api", Router::new())
+        .register("*", Router::new())
+        .register("*.api", Router::new())
+        .register("h2:api", Router::new());
+
+    assert_eq!(layer.subdomains(), ["api"]);
+    assert_eq!(layer.len(), 1);
+    assert!(!layer.is_empty());
+
+    let layer = SubdomainLayer::new().register("*", Router::new());
+    assert_eq!(layer.len(), 0);
+    assert!(layer.is_empty());
+}
+
+#[tokio::test]
+async fn test_unregister() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", Router::new().route("/", get(|| async { "api" })))
+                .register("admin", Router::new().route("/", get(|| async { "admin" })))
+                .unregister(" Admin ")
+                .unregister("missing"),
+        );
+
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "api");
+
+    let resp = test_util::get(&app, "admin.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+}
+
+#[tokio::test]
+async fn test_from_map() {
+    let routes = HashMap::from([
+        (
+            "api".to_string(),
+            Router::new().route("/", get(|| async { "api" })),
+        ),
+        (
+            " Admin ".to_string(),
+            Router::new().route("/", get(|| async { "admin" })),
+        ),
+    ]);
+    let layer = SubdomainLayer::from_map(routes).strict(true);
+    assert_eq!(layer.len(), 2);
+
+    let app = Router::new().layer(layer);
+    for (host, expected) in [("api.example.com", "api"), ("admin.example.com", "admin")] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+
+    let resp = test_util::get(&app, "www.example.com", "/").await;
+    assert_eq!(resp.status(), 404);
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "request-id")]
+
+mod common;
+
+use axum::{Router, http::HeaderMap, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_inject_request_id() {
+    // Echo the request ID the handler received
+    let api_router = Router::new().route(
+        "/",
+        get(|headers: HeaderMap| async move {
+            headers
+                .get("x-request-id")
+                .map(|v| v.to_str().unwrap().to_string())
+                .unwrap_or_default()
+        }),
+    );
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .inject_request_id(true),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test a request ID is generated when absent
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let echoed = resp.headers().get("x-request-id").unwrap().clone();
+    let seen = resp.text().await.unwrap();
+    assert_eq!(echoed, seen.as_str());
+    assert!(uuid::Uuid::parse_str(&seen).is_ok());
+
+    // Test an existing request ID is preserved
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .header("X-Request-Id", "req-123")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "req-123");
+    assert_eq!(resp.text().await.unwrap(), "req-123");
+
+    // Test the apex is not affected
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.headers().get("x-request-id").is_none());
+}
@@ -0,0 +1,39 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(require_tld: bool) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .known_hosts(vec!["internal.lan".to_string()])
+                .require_tld(require_tld),
+        )
+}
+
+#[tokio::test]
+async fn test_tldless_hosts_by_default() {
+    let resp = test_util::get(&app(false), "api.myhost", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+}
+
+#[tokio::test]
+async fn test_require_tld() {
+    let app = app(true);
+
+    for (host, expected) in [
+        // Test hosts without a known TLD have no subdomain
+        ("api.myhost", "Hello from Main App!"),
+        ("myhost", "Hello from Main App!"),
+        // Test known TLDs and known hosts still extract subdomains
+        ("api.example.com", "Hello from API!"),
+        ("api.internal.lan", "Hello from API!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
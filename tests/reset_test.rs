@@ -0,0 +1,102 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+fn routers() -> (Router, Router) {
+    let api_router = Router::new().route("/", get(|| async { "Hello from API!" }));
+    let nested_router = Router::new().route("/", get(|| async { "Hello from API Example!" }));
+    (api_router, nested_router)
+}
+
+#[tokio::test]
+async fn test_without_known_hosts() {
+    let (api_router, nested_router) = routers();
+    let base = SubdomainLayer::new()
+        .known_hosts(vec!["example.test".to_string()])
+        .register("api", api_router)
+        .register("api.example", nested_router);
+
+    let with_hosts = common::serve(
+        Router::new()
+            .route("/", get(|| async { "Hello from Main App!" }))
+            .layer(base.clone()),
+    )
+    .await;
+    let without_hosts = common::serve(
+        Router::new()
+            .route("/", get(|| async { "Hello from Main App!" }))
+            .layer(base.without_known_hosts()),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    // Known host suffix is stripped
+    let resp = client
+        .get(format!("http://{}", with_hosts))
+        .header("Host", "api.example.test")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    // After the reset, `test` is not a known TLD so only its label is dropped
+    let resp = client
+        .get(format!("http://{}", without_hosts))
+        .header("Host", "api.example.test")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API Example!");
+}
+
+#[tokio::test]
+async fn test_without_tlds() {
+    let (api_router, nested_router) = routers();
+    let base = SubdomainLayer::new()
+        .register("api", api_router)
+        .register("api.example", nested_router);
+
+    let with_tlds = common::serve(
+        Router::new()
+            .route("/", get(|| async { "Hello from Main App!" }))
+            .layer(base.clone()),
+    )
+    .await;
+    let without_tlds = common::serve(
+        Router::new()
+            .route("/", get(|| async { "Hello from Main App!" }))
+            .layer(base.without_tlds()),
+    )
+    .await;
+
+    let client = reqwest::Client::new();
+
+    // `com` is stripped as a known TLD
+    let resp = client
+        .get(format!("http://{}", with_tlds))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    // With no TLDs, `com` is treated as the domain
+    let resp = client
+        .get(format!("http://{}", without_tlds))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API Example!");
+
+    // The apex now has a subdomain (`example`) which falls back to main
+    let resp = client
+        .get(format!("http://{}", without_tlds))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+}
@@ -0,0 +1,80 @@
+use axum::{
+    Router,
+    extract::Request,
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use axum_subdomain_routing::{RetryAfter, SubdomainLayer, test_util};
+use std::time::{Duration, SystemTime};
+
+/// A stand-in rate limiter rejecting every request.
+async fn rate_limited(_req: Request, _next: Next) -> Response {
+    StatusCode::TOO_MANY_REQUESTS.into_response()
+}
+
+#[tokio::test]
+async fn test_retry_after_on_rate_limited_response() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register_with_layer(
+                "api",
+                Router::new().route("/", get(|| async { "api" })),
+                middleware::from_fn(rate_limited),
+            )
+            .retry_after(RetryAfter::Seconds(120)),
+    );
+
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers()[header::RETRY_AFTER], "120");
+}
+
+#[tokio::test]
+async fn test_retry_after_on_maintenance_response() {
+    // 2026-10-21T07:28:00Z
+    let date = SystemTime::UNIX_EPOCH + Duration::from_secs(1_792_567_680);
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .global_maintenance(true, || StatusCode::SERVICE_UNAVAILABLE.into_response())
+            .retry_after(RetryAfter::Date(date)),
+    );
+
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        resp.headers()[header::RETRY_AFTER],
+        "Wed, 21 Oct 2026 07:28:00 GMT"
+    );
+}
+
+#[tokio::test]
+async fn test_retry_after_only_on_429_and_503() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route(
+                        "/",
+                        get(|| async {
+                            (
+                                StatusCode::SERVICE_UNAVAILABLE,
+                                [(header::RETRY_AFTER, "5")],
+                            )
+                        }),
+                    ),
+                )
+                .retry_after(RetryAfter::Seconds(120)),
+        );
+
+    // Test existing headers are kept
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(resp.headers()[header::RETRY_AFTER], "5");
+
+    // Test successful responses are left alone
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert!(resp.headers().get(header::RETRY_AFTER).is_none());
+}
@@ -0,0 +1,119 @@
+use axum::{Router, body::Body, http::Request, routing::post};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(layer: SubdomainLayer) -> Router {
+    let tenant = || {
+        Router::new().route(
+            "/",
+            post(|| async { "saved" }).get(|| async { "Hello from App!" }),
+        )
+    };
+
+    Router::new().layer(
+        layer
+            .register("app", tenant())
+            .register("open", tenant())
+            .enforce_same_origin("app"),
+    )
+}
+
+fn request(method: &str, host: &str, header: (&str, &str)) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri("/")
+        .header("host", host)
+        .header(header.0, header.1)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_enforce_same_origin() {
+    let app = app(SubdomainLayer::new());
+
+    // Test a foreign Origin is rejected
+    let req = request(
+        "POST",
+        "app.example.com",
+        ("origin", "https://evil.example.org"),
+    );
+    assert_eq!(test_util::send(&app, req).await.status(), 403);
+
+    // Test a foreign Referer is rejected when Origin is absent
+    let req = request(
+        "POST",
+        "app.example.com",
+        ("referer", "https://evil.example.org/form"),
+    );
+    assert_eq!(test_util::send(&app, req).await.status(), 403);
+
+    // Test the same Origin routes
+    let req = request(
+        "POST",
+        "app.example.com",
+        ("origin", "https://app.example.com"),
+    );
+    assert_eq!(
+        test_util::text(test_util::send(&app, req).await).await,
+        "saved"
+    );
+
+    // Test safe methods and other subdomains are not checked
+    let req = request(
+        "GET",
+        "app.example.com",
+        ("origin", "https://evil.example.org"),
+    );
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(test_util::text(resp).await, "Hello from App!");
+
+    let req = request(
+        "POST",
+        "open.example.com",
+        ("origin", "https://evil.example.org"),
+    );
+    assert_eq!(
+        test_util::text(test_util::send(&app, req).await).await,
+        "saved"
+    );
+}
+
+#[tokio::test]
+async fn test_same_origin_without_host_header() {
+    // Test an HTTP/2-style request carrying the host only in its URI authority
+    let app = app(SubdomainLayer::new());
+    let req = Request::builder()
+        .method("POST")
+        .uri("https://app.example.com/")
+        .header("origin", "https://app.example.com")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        test_util::text(test_util::send(&app, req).await).await,
+        "saved"
+    );
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("https://app.example.com/")
+        .header("origin", "https://evil.example.org")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(test_util::send(&app, req).await.status(), 403);
+}
+
+#[tokio::test]
+async fn test_same_origin_behind_trusted_proxy() {
+    let app = app(SubdomainLayer::new().trust_forwarded_host(true));
+    let mut req = request(
+        "POST",
+        "upstream.internal",
+        ("origin", "https://app.example.com"),
+    );
+    req.headers_mut()
+        .insert("x-forwarded-host", "app.example.com".parse().unwrap());
+    assert_eq!(
+        test_util::text(test_util::send(&app, req).await).await,
+        "saved"
+    );
+}
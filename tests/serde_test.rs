@@ -0,0 +1,43 @@
+#![cfg(feature = "serde")]
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{RequestInfo, SubdomainLayer, test_util};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_serialize_routing_records() {
+    let api_router = Router::new().route("/users", get(|| async { "Hello from API!" }));
+    let seen: Arc<Mutex<Vec<RequestInfo>>> = Arc::default();
+
+    let (layer, mut records) = SubdomainLayer::new()
+        .register("api", api_router)
+        .on_request({
+            let seen = seen.clone();
+            move |info| seen.lock().unwrap().push(info.clone())
+        })
+        .access_log_channel(4);
+    let app = Router::new().layer(layer);
+
+    test_util::get(&app, "api.example.com", "/users").await;
+
+    // Test a matched routing decision serializes with plain JSON values
+    let info = seen.lock().unwrap()[0].clone();
+    assert_eq!(
+        serde_json::to_value(&info).unwrap(),
+        json!({
+            "raw_host": "api.example.com",
+            "subdomain": "api",
+            "matched": "api",
+            "method": "GET",
+            "path": "/users",
+        })
+    );
+
+    // Test the access record carries the numeric status
+    let record = serde_json::to_value(records.recv().await.unwrap()).unwrap();
+    assert_eq!(record["subdomain"], "api");
+    assert_eq!(record["method"], "GET");
+    assert_eq!(record["status"], 200);
+    assert!(record["duration"]["nanos"].is_u64());
+}
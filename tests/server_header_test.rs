@@ -0,0 +1,39 @@
+use axum::{
+    Router,
+    http::{HeaderValue, header},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_register_with_server_header() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register_with_server_header(
+                    "api",
+                    HeaderValue::from_static("api-gateway/1.0"),
+                    Router::new().route("/", get(|| async { "api" })),
+                )
+                .register_with_server_header(
+                    "admin",
+                    HeaderValue::from_static("admin-console"),
+                    Router::new().route(
+                        "/",
+                        get(|| async { ([(header::SERVER, "overridden")], "admin") }),
+                    ),
+                ),
+        );
+
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(resp.headers()[header::SERVER], "api-gateway/1.0");
+
+    // Test the router's own Server header is overridden
+    let resp = test_util::get(&app, "admin.example.com", "/").await;
+    assert_eq!(resp.headers()[header::SERVER], "admin-console");
+
+    // Test the apex is unaffected
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert!(resp.headers().get(header::SERVER).is_none());
+}
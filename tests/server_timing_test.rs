@@ -0,0 +1,35 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_server_timing() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .server_timing(true),
+        );
+
+    // Test matched responses report a parseable routing duration
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    let timing = resp.headers()["server-timing"].to_str().unwrap();
+    let duration = timing.strip_prefix("subdomain;dur=").unwrap();
+    assert!(duration.parse::<f64>().unwrap() >= 0.0);
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+
+    // Test unmatched responses carry no timing
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert!(!resp.headers().contains_key("server-timing"));
+}
+
+#[tokio::test]
+async fn test_server_timing_disabled_by_default() {
+    let app = Router::new().layer(SubdomainLayer::new().register("api", Router::new()));
+
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert!(!resp.headers().contains_key("server-timing"));
+}
@@ -0,0 +1,56 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{Extraction, SubdomainLayer, test_util};
+
+/// A tenant router wrapped in its own layer, counting how often that layer parses the host.
+fn tenant_app(reuse: bool, parses: Arc<AtomicUsize>) -> Router {
+    let inner_layer = SubdomainLayer::new()
+        .register(
+            "acme",
+            Router::new().route("/", get(|| async { "acme dashboard" })),
+        )
+        .reuse_matched_subdomain(reuse)
+        .extractor(move |host| {
+            parses.fetch_add(1, Ordering::SeqCst);
+            Extraction {
+                key: host.split('.').next().map(str::to_string),
+                effective_host: host.to_string(),
+            }
+        });
+
+    let tenant_router = Router::new()
+        .route("/", get(|| async { "generic tenant" }))
+        .layer(inner_layer);
+
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register("*", tenant_router))
+}
+
+#[tokio::test]
+async fn test_stacked_layers_reuse_matched_subdomain() {
+    let parses = Arc::new(AtomicUsize::new(0));
+    let app = tenant_app(true, parses.clone());
+
+    // Test the inner layer routes on the outer match without parsing the host
+    let resp = test_util::get(&app, "acme.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "acme dashboard");
+    let resp = test_util::get(&app, "globex.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "generic tenant");
+    assert_eq!(parses.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_stacked_layers_parse_without_reuse() {
+    let parses = Arc::new(AtomicUsize::new(0));
+    let app = tenant_app(false, parses.clone());
+
+    // Test the same routing, at the cost of parsing the host again
+    let resp = test_util::get(&app, "acme.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "acme dashboard");
+    assert!(parses.load(Ordering::SeqCst) > 0);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "static-dir")]
+
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+use std::fs;
+
+#[tokio::test]
+async fn test_register_static_dir_tree() {
+    let root = std::env::temp_dir().join(format!("subdomain-sites-{}", std::process::id()));
+    fs::create_dir_all(root.join("api")).unwrap();
+    fs::create_dir_all(root.join("docs")).unwrap();
+    fs::write(root.join("api/index.html"), "api site").unwrap();
+    fs::write(root.join("docs/guide.txt"), "docs guide").unwrap();
+    fs::write(root.join("README"), "not a site").unwrap();
+
+    let layer = SubdomainLayer::new()
+        .register_static_dir_tree(&root)
+        .unwrap();
+    assert_eq!(layer.len(), 2);
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer);
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test each folder is served on its subdomain
+    let resp = client
+        .get(format!("http://{}/index.html", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "api site");
+
+    let resp = client
+        .get(format!("http://{}/guide.txt", addr))
+        .header("Host", "docs.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "docs guide");
+
+    // Test files are not shared between sites
+    let resp = client
+        .get(format!("http://{}/guide.txt", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+
+    fs::remove_dir_all(&root).unwrap();
+}
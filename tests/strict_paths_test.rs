@@ -0,0 +1,65 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::SubdomainLayer;
+
+#[tokio::test]
+async fn test_strict_for_paths() {
+    let api_router = Router::new().route("/api/x", get(|| async { "Hello from API!" }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .route("/api/x", get(|| async { "Main API" }))
+        .route("/apix", get(|| async { "Main apix" }))
+        .layer(
+            SubdomainLayer::new()
+                .register("api", api_router)
+                .strict_for_paths(vec!["/api".to_string()]),
+        );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test unknown subdomains 404 under a strict prefix
+    let resp = client
+        .get(format!("http://{}/api/x", addr))
+        .header("Host", "unknown.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+
+    // Test other paths fall back, including ones merely sharing the prefix text
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "unknown.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Main App!");
+
+    let resp = client
+        .get(format!("http://{}/apix", addr))
+        .header("Host", "unknown.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Main apix");
+
+    // Test known subdomains and the apex are unaffected
+    let resp = client
+        .get(format!("http://{}/api/x", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    let resp = client
+        .get(format!("http://{}/api/x", addr))
+        .header("Host", "example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Main API");
+}
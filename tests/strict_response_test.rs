@@ -0,0 +1,48 @@
+use axum::{
+    Json, Router,
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_custom_strict_response() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .strict(true)
+                .strict_response(|| {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(json!({ "error": "unknown tenant" })),
+                    )
+                        .into_response()
+                }),
+        );
+
+    // Test unknown subdomains get the custom body and content type
+    let resp = test_util::get(&app, "unknown.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(test_util::text(resp).await, r#"{"error":"unknown tenant"}"#);
+
+    // Test known subdomains are unaffected
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from API!");
+}
+
+#[tokio::test]
+async fn test_default_strict_response_is_empty() {
+    let app = Router::new().layer(SubdomainLayer::new().strict(true));
+
+    let resp = test_util::get(&app, "unknown.example.com", "/").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(test_util::text(resp).await, "");
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{HeaderMap, HeaderName, Method, Request},
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+async fn token(headers: HeaderMap) -> String {
+    match headers.get("x-internal-token") {
+        Some(value) => value.to_str().unwrap().to_string(),
+        None => "missing".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_strip_request_headers() {
+    let app = Router::new().route("/", get(token)).layer(
+        SubdomainLayer::new()
+            .register("public", Router::new().route("/", get(token)))
+            .register("admin", Router::new().route("/", get(token)))
+            .strip_request_headers("public", &[HeaderName::from_static("x-internal-token")]),
+    );
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test the header is removed for the stripped subdomain
+    let resp = client
+        .get(format!("http://{}/", addr))
+        .header("Host", "public.example.com")
+        .header("X-Internal-Token", "secret")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "missing");
+
+    // Test other subdomains and the main app still see it
+    for host in ["admin.example.com", "example.com"] {
+        let resp = client
+            .get(format!("http://{}/", addr))
+            .header("Host", host)
+            .header("X-Internal-Token", "secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "secret");
+    }
+}
+
+#[tokio::test]
+async fn test_policies_apply_to_wildcard_keys() {
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("*", Router::new().route("/", get(token)))
+            .register("*.api", Router::new().route("/", get(token)))
+            .strip_request_headers("*", &[HeaderName::from_static("x-internal-token")])
+            .allowed_methods("*.api", &[Method::POST]),
+    );
+
+    // Test the header is removed for every subdomain the wildcard routes
+    let req = Request::builder()
+        .uri("/")
+        .header("host", "tenant1.example.com")
+        .header("x-internal-token", "secret")
+        .body(Body::empty())
+        .unwrap();
+    let resp = test_util::send(&app, req).await;
+    assert_eq!(test_util::text(resp).await, "missing");
+
+    // Test a `*.api` policy applies to `tenant.api`
+    let resp = test_util::get(&app, "tenant.api.example.com", "/").await;
+    assert_eq!(resp.status(), 405);
+}
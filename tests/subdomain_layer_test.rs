@@ -0,0 +1,45 @@
+use axum::{
+    Router,
+    http::{HeaderValue, StatusCode},
+    middleware,
+    response::Response,
+    routing::get,
+};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+async fn mark_admin(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert("x-admin", HeaderValue::from_static("1"));
+    response
+}
+
+#[tokio::test]
+async fn test_register_with_layer() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register_with_layer(
+                    "admin",
+                    Router::new().route("/", get(|| async { "admin" })),
+                    middleware::map_response(mark_admin),
+                )
+                .register("api", Router::new().route("/", get(|| async { "api" }))),
+        );
+
+    // Test the subdomain's layer runs for its routes and fallback
+    let resp = test_util::get(&app, "admin.example.com", "/").await;
+    assert_eq!(resp.headers()["x-admin"], "1");
+    assert_eq!(test_util::text(resp).await, "admin");
+
+    let resp = test_util::get(&app, "admin.example.com", "/missing").await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(resp.headers()["x-admin"], "1");
+
+    // Test other subdomains and the apex are not wrapped
+    for host in ["api.example.com", "example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert!(resp.headers().get("x-admin").is_none(), "{host}");
+    }
+}
@@ -0,0 +1,25 @@
+use axum::Router;
+use axum_subdomain_routing::SubdomainLayer;
+
+fn layer() -> SubdomainLayer {
+    SubdomainLayer::new()
+        .register("api", Router::new())
+        .register("admin", Router::new())
+        .register("*.tenants", Router::new())
+}
+
+#[test]
+fn test_suggest_nearest_subdomain() {
+    let layer = layer();
+    assert_eq!(layer.suggest("admn").as_deref(), Some("admin"));
+    assert_eq!(layer.suggest("Admn").as_deref(), Some("admin"));
+    assert_eq!(layer.suggest("apii").as_deref(), Some("api"));
+}
+
+#[test]
+fn test_suggest_rejects_dissimilar_input() {
+    let layer = layer();
+    assert_eq!(layer.suggest("marketing"), None);
+    assert_eq!(layer.suggest("x"), None);
+    assert_eq!(SubdomainLayer::new().suggest("api"), None);
+}
@@ -0,0 +1,65 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(layer: SubdomainLayer) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            layer
+                .register("api", Router::new().route("/", get(|| async { "api" })))
+                .register(
+                    "api.example",
+                    Router::new().route("/", get(|| async { "api.example" })),
+                )
+                .strict(true),
+        )
+}
+
+#[tokio::test]
+async fn test_custom_multi_label_tld() {
+    let app = app(SubdomainLayer::new().tlds(vec!["co.uk".to_string(), "uk".to_string()]));
+
+    // Test the longest configured suffix is stripped
+    let resp = test_util::get(&app, "api.example.co.uk", "/").await;
+    assert_eq!(test_util::text(resp).await, "api");
+
+    let resp = test_util::get(&app, "example.co.uk", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+
+    // Test TLDs outside the replaced list are no longer stripped
+    let resp = test_util::get(&app, "api.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "api.example");
+}
+
+#[tokio::test]
+async fn test_add_tlds_augments_defaults() {
+    let app = app(SubdomainLayer::new().add_tlds(vec!["xyz".to_string(), ".app".to_string()]));
+
+    for host in ["api.example.xyz", "api.example.app", "api.example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, "api", "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_default_multi_label_suffixes() {
+    let app = app(SubdomainLayer::new());
+
+    for suffix in ["co.uk", "gov.uk", "com.tr"] {
+        // Test the subdomain in front of a multi-label suffix
+        let resp = test_util::get(&app, &format!("api.example.{suffix}"), "/").await;
+        assert_eq!(test_util::text(resp).await, "api", "{suffix}");
+
+        // Test the bare apex maps to the main router
+        let resp = test_util::get(&app, &format!("example.{suffix}"), "/").await;
+        assert_eq!(
+            test_util::text(resp).await,
+            "Hello from Main App!",
+            "{suffix}"
+        );
+    }
+
+    // Test single-label TLDs keep working
+    let resp = test_util::get(&app, "api.example.uk", "/").await;
+    assert_eq!(test_util::text(resp).await, "api");
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "tracing")]
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{Layer, layer::Context, prelude::*};
+
+type Fields = HashMap<String, String>;
+
+/// Records the fields of every routing decision event.
+#[derive(Clone, Default)]
+struct EventRecorder(Arc<Mutex<Vec<Fields>>>);
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+impl<S: Subscriber> Layer<S> for EventRecorder {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() == "axum_subdomain_routing" {
+            let mut fields = Fields::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.0.lock().unwrap().push(fields);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_routing_decision_events() {
+    let recorder = EventRecorder::default();
+    let _guard = tracing_subscriber::registry()
+        .with(recorder.clone())
+        .set_default();
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .register(
+                    "*.tenants",
+                    Router::new().route("/", get(|| async { "tenant" })),
+                ),
+        );
+
+    for host in [
+        "api.example.com:8080",
+        "acme.tenants.example.com",
+        "www.example.com",
+        "example.com",
+    ] {
+        test_util::get(&app, host, "/").await;
+    }
+
+    let events = recorder.0.lock().unwrap();
+    let summary: Vec<_> = events
+        .iter()
+        .map(|fields| {
+            (
+                fields["host"].as_str(),
+                fields.get("subdomain").map(String::as_str),
+                fields["outcome"].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("api.example.com:8080", Some("api"), "exact"),
+            ("acme.tenants.example.com", Some("acme.tenants"), "wildcard"),
+            ("www.example.com", Some("www"), "fallback"),
+            ("example.com", None, "apex"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_strict_not_found_event() {
+    let recorder = EventRecorder::default();
+    let _guard = tracing_subscriber::registry()
+        .with(recorder.clone())
+        .set_default();
+
+    let app = Router::new().layer(SubdomainLayer::new().strict(true));
+    test_util::get(&app, "www.example.com", "/").await;
+
+    let events = recorder.0.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["subdomain"], "www");
+    assert_eq!(events[0]["outcome"], "strict_not_found");
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    http::{HeaderMap, HeaderValue, Request},
+    response::Response,
+    routing::get,
+};
+use axum_subdomain_routing::SubdomainLayer;
+use http_body_util::{BodyExt, StreamBody, combinators::BoxBody};
+use hyper::body::Frame;
+use hyper_util::{client::legacy::Client, rt::TokioExecutor};
+use std::convert::Infallible;
+
+fn response_with_trailers() -> Response {
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    trailers.insert("x-checksum", HeaderValue::from_static("abc123"));
+
+    let frames = futures::stream::iter([
+        Ok::<_, Infallible>(Frame::data(Bytes::from_static(b"Hello from gRPC!"))),
+        Ok(Frame::trailers(trailers)),
+    ]);
+
+    // HTTP/1.1 only transmits trailer fields announced in the `Trailer` header.
+    Response::builder()
+        .header("Trailer", "grpc-status, x-checksum")
+        .body(Body::new(StreamBody::new(frames)))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_trailers_preserved_end_to_end() {
+    let grpc_router = Router::new().route("/", get(|| async { response_with_trailers() }));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register("grpc", grpc_router));
+
+    let addr = common::serve(app).await;
+    let client = Client::builder(TokioExecutor::new()).build_http::<BoxBody<Bytes, Infallible>>();
+
+    let req = Request::builder()
+        .uri(format!("http://{}/", addr))
+        .header("Host", "grpc.example.com")
+        .header("TE", "trailers")
+        .body(BoxBody::default())
+        .unwrap();
+    let resp = client.request(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let collected = resp.into_body().collect().await.unwrap();
+    let trailers = collected
+        .trailers()
+        .cloned()
+        .expect("trailers were stripped");
+    assert_eq!(trailers.get("grpc-status").unwrap(), "0");
+    assert_eq!(trailers.get("x-checksum").unwrap(), "abc123");
+    assert_eq!(collected.to_bytes(), "Hello from gRPC!");
+}
@@ -0,0 +1,41 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainLayer, test_util};
+
+fn app(layer: SubdomainLayer) -> Router {
+    Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            layer
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "Hello from API!" })),
+                )
+                .register_apex_for(
+                    "example.org",
+                    Router::new().route("/", get(|| async { "org" })),
+                ),
+        )
+}
+
+#[tokio::test]
+async fn test_trailing_dot_hosts() {
+    for layer in [
+        SubdomainLayer::new(),
+        SubdomainLayer::new().known_hosts(vec!["example.com".to_string()]),
+    ] {
+        let app = app(layer);
+        for (host, expected) in [
+            // Test fully-qualified hosts route like their dotless form
+            ("example.com.", "Hello from Main App!"),
+            ("api.example.com.", "Hello from API!"),
+            ("api.example.com.:8080", "Hello from API!"),
+            ("example.org.", "org"),
+            // Test dotless hosts are unchanged
+            ("example.com", "Hello from Main App!"),
+            ("api.example.com", "Hello from API!"),
+        ] {
+            let resp = test_util::get(&app, host, "/").await;
+            assert_eq!(test_util::text(resp).await, expected, "{host}");
+        }
+    }
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{SubdomainError, SubdomainLayer};
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn test_try_from_map() {
+    let mut routes = HashMap::new();
+    routes.insert(
+        "api".to_string(),
+        Router::new().route("/", get(|| async { "Hello from API!" })),
+    );
+    routes.insert(
+        " admin. ".to_string(),
+        Router::new().route("/", get(|| async { "Hello from Admin!" })),
+    );
+
+    let layer = SubdomainLayer::try_from(routes).unwrap();
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(layer);
+
+    let addr = common::serve(app).await;
+    let client = reqwest::Client::new();
+
+    // Test plain key
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from API!");
+
+    // Test normalized key
+    let resp = client
+        .get(format!("http://{}", addr))
+        .header("Host", "admin.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.text().await.unwrap(), "Hello from Admin!");
+}
+
+#[test]
+fn test_try_from_map_rejects_empty_key() {
+    for key in ["", "  ", "."] {
+        let mut routes = HashMap::new();
+        routes.insert(key.to_string(), Router::new());
+
+        let err = SubdomainLayer::try_from(routes).err().unwrap();
+        assert_eq!(err, SubdomainError::EmptySubdomain);
+    }
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use axum::{
+    Router,
+    extract::ws::{Message, WebSocketUpgrade},
+    response::Response,
+    routing::get,
+};
+use axum_subdomain_routing::SubdomainLayer;
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
+
+async fn echo(ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(|mut socket| async move {
+        while let Some(Ok(Message::Text(text))) = socket.recv().await {
+            if socket
+                .send(Message::Text(format!("echo: {text}").into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_websocket_upgrade_through_layer() {
+    let ws_router = Router::new().route("/socket", get(echo));
+
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register("ws", ws_router));
+
+    let addr = common::serve(app).await;
+
+    let mut request = format!("ws://{}/socket", addr)
+        .into_client_request()
+        .unwrap();
+    request
+        .headers_mut()
+        .insert("Host", "ws.example.com".parse().unwrap());
+
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let (mut socket, response) = tokio_tungstenite::client_async(request, stream)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 101);
+
+    socket
+        .send(tungstenite::Message::Text("hello".into()))
+        .await
+        .unwrap();
+    let reply = socket.next().await.unwrap().unwrap();
+    assert_eq!(reply, tungstenite::Message::Text("echo: hello".into()));
+}
@@ -0,0 +1,114 @@
+use axum::{Router, routing::get};
+use axum_subdomain_routing::{MatchedSubdomain, SubdomainLayer, test_util};
+
+#[tokio::test]
+async fn test_wildcard_subdomains() {
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "api",
+                    Router::new().route("/", get(|| async { "exact api" })),
+                )
+                .register(
+                    "*",
+                    Router::new().route("/", get(|| async { "any tenant" })),
+                )
+                .register(
+                    "*.api",
+                    Router::new().route("/", get(|| async { "tenant api" })),
+                ),
+        );
+
+    for (host, expected) in [
+        // Test tenants share the wildcard router
+        ("tenant1.example.com", "any tenant"),
+        ("tenant2.example.com", "any tenant"),
+        // Test exact registrations win over wildcards
+        ("api.example.com", "exact api"),
+        // Test suffix wildcards cover one label in front of the suffix
+        ("tenant1.api.example.com", "tenant api"),
+        // Test deeper subdomains are not covered by a single-label wildcard
+        ("a.b.example.com", "Hello from Main App!"),
+        ("example.com", "Hello from Main App!"),
+    ] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, expected, "{host}");
+    }
+}
+
+#[tokio::test]
+async fn test_wildcard_only_with_empty_suffix() {
+    // Test `*.` normalizes to the plain wildcard, with no exact routes at all
+    let app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(
+            SubdomainLayer::new()
+                .register(
+                    "*.",
+                    Router::new().route("/", get(|| async { "any tenant" })),
+                )
+                .strict(true),
+        );
+
+    for host in ["api.example.com", "tenant1.example.com"] {
+        let resp = test_util::get(&app, host, "/").await;
+        assert_eq!(test_util::text(resp).await, "any tenant", "{host}");
+    }
+
+    // Test unmatched deeper subdomains still follow strict mode
+    let resp = test_util::get(&app, "a.b.example.com", "/").await;
+    assert_eq!(resp.status(), 404);
+
+    // Test the apex is unaffected
+    let resp = test_util::get(&app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+}
+
+#[tokio::test]
+async fn test_wildcard_exposes_concrete_subdomain() {
+    let echo = || {
+        Router::new().route(
+            "/",
+            get(|MatchedSubdomain(sub): MatchedSubdomain| async move { sub }),
+        )
+    };
+    let app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("*", echo())
+            .register("*.tenants", echo()),
+    );
+
+    // Test the handler reads the matched value rather than the wildcard key
+    let resp = test_util::get(&app, "acme.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "acme");
+
+    let resp = test_util::get(&app, "acme.tenants.example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "acme.tenants");
+}
+
+#[tokio::test]
+async fn test_wildcard_only_with_apex() {
+    let tenant = || Router::new().route("/", get(|| async { "any tenant" }));
+    let apex_app = Router::new().layer(
+        SubdomainLayer::new()
+            .register("*", tenant())
+            .apex_router(Router::new().route("/", get(|| async { "apex" }))),
+    );
+    let inner_app = Router::new()
+        .route("/", get(|| async { "Hello from Main App!" }))
+        .layer(SubdomainLayer::new().register("*", tenant()));
+
+    // Test the wildcard routes without any exact registrations
+    for app in [&apex_app, &inner_app] {
+        let resp = test_util::get(app, "anything.example.com", "/").await;
+        assert_eq!(test_util::text(resp).await, "any tenant");
+    }
+
+    // Test the apex reaches the apex router, or the inner service without one
+    let resp = test_util::get(&apex_app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "apex");
+    let resp = test_util::get(&inner_app, "example.com", "/").await;
+    assert_eq!(test_util::text(resp).await, "Hello from Main App!");
+}